@@ -0,0 +1,62 @@
+//! Performance baselines for the simulation hot paths
+//!
+//! Run with `cargo bench`. Covers a single swing simulation, the full
+//! regeneration temperature sweep at several resolutions, and a
+//! 1000-county batch in serial and parallel.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use mof_thermal_dynamics::*;
+
+fn simulator() -> ThermalFluidSimulator {
+    ThermalFluidSimulator::new(MOFThermalProperties::default(), OperatingConditions::default())
+}
+
+fn bench_single_simulation(c: &mut Criterion) {
+    let simulator = simulator();
+    c.bench_function("simulate_temperature_swing", |b| {
+        b.iter(|| black_box(&simulator).simulate_temperature_swing().unwrap())
+    });
+}
+
+fn bench_optimization_sweep(c: &mut Criterion) {
+    // 10 K is the default; 1 K is the finest resolution planners use
+    let mut group = c.benchmark_group("optimize_conditions_with");
+    for step_k in [10.0, 5.0, 1.0] {
+        let config = OptimizationConfig {
+            temp_step_k: step_k,
+            ..Default::default()
+        };
+        group.bench_with_input(BenchmarkId::new("step_k", step_k), &config, |b, config| {
+            b.iter(|| simulator().optimize_conditions_with(config, None).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_batch(c: &mut Criterion) {
+    // Vary properties across counties so every entry does real work
+    let mut store = MofPropertyStore::new();
+    let fips_list: Vec<u32> = (1..=1000).collect();
+    for &fips in &fips_list {
+        let spread = f64::from(fips % 10) / 10.0;
+        store.insert(MOFThermalProperties {
+            fips,
+            thermal_conductivity: 0.4 + 0.2 * spread,
+            max_uptake_g_per_g: 0.2 + 0.15 * spread,
+            ..Default::default()
+        });
+    }
+    let conditions = OperatingConditions::default();
+
+    let mut group = c.benchmark_group("batch_1000_fips");
+    group.bench_function("serial", |b| {
+        b.iter(|| simulate_batch(&store, black_box(&fips_list), &conditions, None))
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| simulate_batch_parallel(&store, black_box(&fips_list), &conditions))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_single_simulation, bench_optimization_sweep, bench_batch);
+criterion_main!(benches);
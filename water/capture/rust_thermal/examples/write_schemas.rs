@@ -0,0 +1,22 @@
+//! Write JSON Schema files for the Python pipeline's data contract
+//!
+//! `cargo run --example write_schemas --features schemars -- [DIR]` writes
+//! one `<TypeName>.schema.json` per type into DIR (default `schemas`).
+
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let dir = std::env::args().nth(1).unwrap_or_else(|| "schemas".to_string());
+    match mof_thermal_dynamics::write_json_schemas(&dir) {
+        Ok(paths) => {
+            for path in paths {
+                println!("{}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,25 @@
+//! Feed arbitrary bytes to the CSV property loader
+//!
+//! Run with `cargo +nightly fuzz run csv_loader` from the crate root. Any
+//! input must produce properties or a `ThermalError`, never a panic.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mof_thermal_dynamics::{
+    load_mof_properties_from_reader, MofPropertyStore, OperatingConditions, ThermalFluidSimulator,
+};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = load_mof_properties_from_reader(data, 1001);
+    if let Ok(store) = MofPropertyStore::from_reader(data) {
+        // Parsed rows are finite, so simulating them must not panic either
+        for fips in [1001, 1003, 1005] {
+            if let Some(properties) = store.get(fips) {
+                let simulator =
+                    ThermalFluidSimulator::new(properties.clone(), OperatingConditions::default());
+                let _ = simulator.simulate_temperature_swing();
+            }
+        }
+    }
+});
@@ -0,0 +1,70 @@
+//! Async batch entry point for callers running on a tokio runtime
+//!
+//! Enabled with the `async` feature. The simulation itself stays synchronous;
+//! `simulate_batch_async` moves the CPU-bound batch onto tokio's blocking
+//! thread pool so request handlers keep the executor free while it runs.
+
+use crate::{
+    simulate_batch_parallel, MofPropertyStore, OperatingConditions, SimulationResults, ThermalError,
+};
+use std::sync::Arc;
+
+/// Run `simulate_batch_parallel` on tokio's blocking pool
+///
+/// Takes owned inputs because the work outlives the caller's borrow; share a
+/// store across requests by cloning the `Arc`. Results match the synchronous
+/// batch entry for entry. A panic inside the batch is resumed on the awaiting
+/// task rather than swallowed.
+pub async fn simulate_batch_async(
+    store: Arc<MofPropertyStore>,
+    fips_list: Vec<u32>,
+    conditions: OperatingConditions,
+) -> Vec<(u32, Result<SimulationResults, ThermalError>)> {
+    let task = tokio::task::spawn_blocking(move || {
+        simulate_batch_parallel(&store, &fips_list, &conditions)
+    });
+    match task.await {
+        Ok(results) => results,
+        Err(error) => std::panic::resume_unwind(error.into_panic()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::assert_results_close;
+    use crate::{simulate_batch, MOFThermalProperties};
+
+    #[tokio::test]
+    async fn test_simulate_batch_async_matches_sync() {
+        let mut store = MofPropertyStore::new();
+        for fips in [1001, 1003, 1005] {
+            store.insert(MOFThermalProperties {
+                fips,
+                thermal_conductivity: 0.4 + f64::from(fips % 10) / 20.0,
+                ..Default::default()
+            });
+        }
+        let fips_list = vec![1005, 9999, 1001, 1003];
+        let conditions = OperatingConditions::default();
+
+        let expected = simulate_batch(&store, &fips_list, &conditions, None);
+        let actual =
+            simulate_batch_async(Arc::new(store), fips_list.clone(), conditions).await;
+
+        assert_eq!(actual.len(), expected.len());
+        for ((fips, result), (expected_fips, expected_result)) in actual.iter().zip(&expected) {
+            assert_eq!(fips, expected_fips);
+            match (result, expected_result) {
+                (Ok(result), Ok(expected_result)) => {
+                    assert_results_close(result, expected_result, 0.0)
+                }
+                (Err(error), Err(expected_error)) => {
+                    assert_eq!(error.to_string(), expected_error.to_string())
+                }
+                _ => panic!("async and sync results disagree for FIPS {}", fips),
+            }
+        }
+        assert!(matches!(actual[1].1, Err(ThermalError::FipsNotFound(9999))));
+    }
+}
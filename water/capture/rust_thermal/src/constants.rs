@@ -0,0 +1,37 @@
+//! Physical reference constants shared by the models
+//!
+//! Values are the ones the simulation has always used, so changing one
+//! shifts every result; the test below pins them.
+
+/// Universal gas constant (J/(mol·K)), to four significant figures
+pub const R_J_PER_MOL_K: f64 = 8.314;
+
+/// Molar mass of water (g/mol)
+pub const WATER_MOLAR_MASS_G: f64 = 18.015;
+
+/// Latent heat of vaporization of water at 100 °C (kJ/kg)
+pub const LATENT_HEAT_WATER_KJ_KG: f64 = 2257.0;
+
+/// Standard atmosphere (Pa)
+pub const STANDARD_PRESSURE_PA: f64 = 101_325.0;
+
+/// J per kJ
+pub const J_PER_KJ: f64 = 1000.0;
+
+/// g per kg
+pub const G_PER_KG: f64 = 1000.0;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constants_have_standard_values() {
+        assert_eq!(R_J_PER_MOL_K, 8.314);
+        assert_eq!(WATER_MOLAR_MASS_G, 18.015);
+        assert_eq!(LATENT_HEAT_WATER_KJ_KG, 2257.0);
+        assert_eq!(STANDARD_PRESSURE_PA, 101_325.0);
+        assert_eq!(J_PER_KJ, 1000.0);
+        assert_eq!(G_PER_KG, 1000.0);
+    }
+}
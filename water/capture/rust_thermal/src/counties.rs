@@ -0,0 +1,78 @@
+//! County name to FIPS lookup
+//!
+//! Backed by `data/county_fips.csv`, compiled into the crate. The table holds
+//! every Alabama county, where the pipeline's pilot sites are, plus a few
+//! same-name counties elsewhere; append `state,fips,county` rows to extend it.
+//! County names repeat across states, so every lookup takes the state too.
+
+use crate::{load_mof_properties, MOFThermalProperties, ThermalError};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+const COUNTY_TABLE: &str = include_str!("../data/county_fips.csv");
+
+/// (state, county) keys, normalized by `county_key`, to FIPS codes
+fn county_table() -> &'static HashMap<(String, String), u32> {
+    static TABLE: OnceLock<HashMap<(String, String), u32>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        COUNTY_TABLE
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, ',');
+                let state = fields.next()?;
+                let fips = fields.next()?.parse().ok()?;
+                let county = fields.next()?;
+                Some((county_key(county, state), fips))
+            })
+            .collect()
+    })
+}
+
+/// Case-insensitive key with any trailing " County" dropped
+fn county_key(name: &str, state: &str) -> (String, String) {
+    let name = name.trim().to_lowercase();
+    let name = name.strip_suffix(" county").unwrap_or(&name).to_string();
+    (state.trim().to_uppercase(), name)
+}
+
+/// FIPS code for a county, e.g. `("Jefferson County", "AL")` → 1073
+///
+/// `state` is the two-letter postal code. Matching ignores case and the
+/// " County" suffix; `None` if the county is not in the bundled table.
+pub fn county_name_to_fips(name: &str, state: &str) -> Option<u32> {
+    county_table().get(&county_key(name, state)).copied()
+}
+
+/// Load placeholder MOF properties for a county given by name
+///
+/// Resolves the name with `county_name_to_fips`, then defers to
+/// `load_mof_properties`.
+pub fn load_mof_properties_by_name(
+    name: &str,
+    state: &str,
+) -> Result<MOFThermalProperties, ThermalError> {
+    let fips = county_name_to_fips(name, state).ok_or_else(|| {
+        ThermalError::InvalidParameter(format!("unknown county '{}' in {}", name, state))
+    })?;
+    load_mof_properties(fips)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_county_name_to_fips() {
+        assert_eq!(county_name_to_fips("Jefferson County", "AL"), Some(1073));
+        assert_eq!(county_name_to_fips("jefferson", "ky"), Some(21111));
+        assert_eq!(county_name_to_fips("Jefferson County", "ZZ"), None);
+
+        let properties = load_mof_properties_by_name("Mobile County", "AL").unwrap();
+        assert_eq!(properties.fips, 1097);
+        assert!(matches!(
+            load_mof_properties_by_name("Nowhere", "AL"),
+            Err(ThermalError::InvalidParameter(_))
+        ));
+    }
+}
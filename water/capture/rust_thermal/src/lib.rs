@@ -1,20 +1,180 @@
-/// MOF Thermal-Fluid Dynamics Module
-/// Deep-Containment Architecture (DCA) Project
-///
-/// This module provides high-performance thermal-fluid dynamics simulation
-/// for MOF water capture systems in deep SMR containment designs.
-///
-/// Key Features:
-/// - Heat transfer modeling for temperature-swing adsorption
-/// - Fluid dynamics for water vapor transport
-/// - Risk minimization through thermal safety analysis
-/// - Integration with Python MOF selection pipeline
+//! MOF Thermal-Fluid Dynamics Module
+//! Deep-Containment Architecture (DCA) Project
+//!
+//! This module provides high-performance thermal-fluid dynamics simulation
+//! for MOF water capture systems in deep SMR containment designs.
+//!
+//! Key Features:
+//! - Heat transfer modeling for temperature-swing adsorption
+//! - Fluid dynamics for water vapor transport
+//! - Risk minimization through thermal safety analysis
+//! - Integration with Python MOF selection pipeline
 
+use log::{debug, trace, warn};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::StandardNormal;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub mod constants;
+use constants::{
+    G_PER_KG, J_PER_KJ, LATENT_HEAT_WATER_KJ_KG, R_J_PER_MOL_K, STANDARD_PRESSURE_PA,
+    WATER_MOLAR_MASS_G,
+};
+
+pub mod units;
+pub use units::{Atm, KiloJoules, Kelvin, Kilograms};
+
+mod counties;
+pub use counties::{county_name_to_fips, load_mof_properties_by_name};
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "parquet")]
+mod parquet_io;
+#[cfg(feature = "parquet")]
+pub use parquet_io::load_mof_properties_from_parquet;
+
+#[cfg(feature = "schemars")]
+mod schema;
+#[cfg(feature = "schemars")]
+pub use schema::{json_schemas, write_json_schemas};
+
+#[cfg(feature = "async")]
+mod async_batch;
+#[cfg(feature = "async")]
+pub use async_batch::simulate_batch_async;
+
+/// Errors produced by property loading, simulation, and optimization
+#[derive(Debug)]
+pub enum ThermalError {
+    /// No property record exists for the requested FIPS code
+    FipsNotFound(u32),
+    /// Operating conditions violate physical constraints
+    InvalidConditions(String),
+    /// MOF properties are non-physical or would make the model non-finite
+    InvalidProperties(String),
+    /// Property data could not be parsed
+    CsvParse(String),
+    /// Parquet property data could not be read or parsed
+    #[cfg(feature = "parquet")]
+    Parquet(parquet::errors::ParquetError),
+    /// Underlying I/O failure while reading or writing data
+    Io(io::Error),
+    /// JSON could not be serialized or deserialized
+    Json(serde_json::Error),
+    /// A TOML config file could not be deserialized
+    Toml(toml::de::Error),
+    /// A tuning parameter (step size, range, threshold) is out of bounds
+    InvalidParameter(String),
+    /// Simulated risk exceeded the simulator's allowed threshold
+    UnsafeOperation { risk_score: f64, max_allowed: f64 },
+    /// No candidate operating point satisfied the optimization constraints
+    OptimizationFailed { evaluated: usize },
+    /// Regeneration setpoint at or above the MOF's thermal stability limit
+    StabilityExceeded { regeneration_temp_k: f64, thermal_stability_k: f64 },
+    /// Reported energy components do not add up to the reported total
+    EnergyImbalance(String),
+}
+
+impl fmt::Display for ThermalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThermalError::FipsNotFound(fips) => write!(f, "FIPS code {} not found", fips),
+            ThermalError::InvalidConditions(msg) => {
+                write!(f, "invalid operating conditions: {}", msg)
+            }
+            ThermalError::InvalidProperties(msg) => write!(f, "invalid MOF properties: {}", msg),
+            ThermalError::InvalidParameter(msg) => write!(f, "invalid parameter: {}", msg),
+            ThermalError::CsvParse(msg) => write!(f, "CSV parse error: {}", msg),
+            #[cfg(feature = "parquet")]
+            ThermalError::Parquet(err) => write!(f, "Parquet error: {}", err),
+            ThermalError::Io(err) => write!(f, "I/O error: {}", err),
+            ThermalError::Json(err) => write!(f, "JSON error: {}", err),
+            ThermalError::Toml(err) => write!(f, "TOML error: {}", err),
+            ThermalError::UnsafeOperation { risk_score, max_allowed } => write!(
+                f,
+                "unsafe operation: risk score {:.3} exceeds allowed {:.3}",
+                risk_score, max_allowed
+            ),
+            ThermalError::OptimizationFailed { evaluated } => write!(
+                f,
+                "optimization failed: none of {} candidates satisfied the constraints",
+                evaluated
+            ),
+            ThermalError::StabilityExceeded { regeneration_temp_k, thermal_stability_k } => write!(
+                f,
+                "regeneration at {:.1} K reaches the {:.1} K stability limit",
+                regeneration_temp_k, thermal_stability_k
+            ),
+            ThermalError::EnergyImbalance(msg) => write!(f, "energy imbalance: {}", msg),
+        }
+    }
+}
+
+impl Error for ThermalError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ThermalError::Io(err) => Some(err),
+            ThermalError::Json(err) => Some(err),
+            ThermalError::Toml(err) => Some(err),
+            #[cfg(feature = "parquet")]
+            ThermalError::Parquet(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ThermalError {
+    fn from(err: io::Error) -> Self {
+        ThermalError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for ThermalError {
+    fn from(err: serde_json::Error) -> Self {
+        ThermalError::Json(err)
+    }
+}
+
+impl From<toml::de::Error> for ThermalError {
+    fn from(err: toml::de::Error) -> Self {
+        ThermalError::Toml(err)
+    }
+}
+
+impl From<csv::Error> for ThermalError {
+    fn from(err: csv::Error) -> Self {
+        if err.is_io_error() {
+            match err.into_kind() {
+                csv::ErrorKind::Io(io_err) => ThermalError::Io(io_err),
+                kind => ThermalError::CsvParse(format!("{:?}", kind)),
+            }
+        } else {
+            ThermalError::CsvParse(err.to_string())
+        }
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl From<parquet::errors::ParquetError> for ThermalError {
+    fn from(err: parquet::errors::ParquetError) -> Self {
+        ThermalError::Parquet(err)
+    }
+}
 
 /// MOF thermal properties for simulation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MOFThermalProperties {
     pub fips: u32,
     pub thermal_conductivity: f64,      // W/(m·K)
@@ -22,196 +182,7213 @@ pub struct MOFThermalProperties {
     pub density: f64,                    // kg/m³
     pub thermal_stability_k: f64,        // K
     pub heat_of_adsorption: f64,         // kJ/mol
+    #[serde(default = "default_langmuir_k")]
+    pub langmuir_k: f64,                 // Langmuir adsorption constant (dimensionless)
+    #[serde(default = "default_max_uptake_g_per_g")]
+    pub max_uptake_g_per_g: f64,         // g water / g MOF at saturation
+    #[serde(default = "default_mass_transfer_coeff")]
+    pub mass_transfer_coeff: f64,        // Linear-driving-force rate constant (1/s)
+}
+
+fn default_langmuir_k() -> f64 {
+    MOFThermalProperties::default().langmuir_k
+}
+
+fn default_max_uptake_g_per_g() -> f64 {
+    MOFThermalProperties::default().max_uptake_g_per_g
+}
+
+fn default_mass_transfer_coeff() -> f64 {
+    MOFThermalProperties::default().mass_transfer_coeff
+}
+
+impl MOFThermalProperties {
+    /// Check the properties are finite and physical, reporting every violation
+    ///
+    /// Conductivity, heat capacity, density and stability limit appear in
+    /// denominators (τ, the risk ratio), so they must be strictly positive.
+    pub fn validate(&self) -> Result<(), ThermalError> {
+        let mut violations = Vec::new();
+
+        let positive = [
+            ("thermal_conductivity", self.thermal_conductivity),
+            ("specific_heat", self.specific_heat),
+            ("density", self.density),
+            ("thermal_stability_k", self.thermal_stability_k),
+            ("mass_transfer_coeff", self.mass_transfer_coeff),
+        ];
+        for (name, value) in positive {
+            if !(is_positive(value) && value.is_finite()) {
+                violations.push(format!("{} must be positive and finite (got {})", name, value));
+            }
+        }
+        let non_negative = [
+            ("heat_of_adsorption", self.heat_of_adsorption),
+            ("langmuir_k", self.langmuir_k),
+            ("max_uptake_g_per_g", self.max_uptake_g_per_g),
+        ];
+        for (name, value) in non_negative {
+            if !(value >= 0.0 && value.is_finite()) {
+                violations.push(format!(
+                    "{} must be non-negative and finite (got {})",
+                    name, value
+                ));
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ThermalError::InvalidProperties(violations.join("; ")))
+        }
+    }
+
+    // Consuming setters for overriding single fields, e.g.
+    // `MOFThermalProperties::default().with_thermal_stability_k(600.0)`
+
+    pub fn with_fips(mut self, value: u32) -> Self {
+        self.fips = value;
+        self
+    }
+
+    pub fn with_thermal_conductivity(mut self, value: f64) -> Self {
+        self.thermal_conductivity = value;
+        self
+    }
+
+    pub fn with_specific_heat(mut self, value: f64) -> Self {
+        self.specific_heat = value;
+        self
+    }
+
+    pub fn with_density(mut self, value: f64) -> Self {
+        self.density = value;
+        self
+    }
+
+    pub fn with_thermal_stability_k(mut self, value: f64) -> Self {
+        self.thermal_stability_k = value;
+        self
+    }
+
+    pub fn with_heat_of_adsorption(mut self, value: f64) -> Self {
+        self.heat_of_adsorption = value;
+        self
+    }
+
+    pub fn with_langmuir_k(mut self, value: f64) -> Self {
+        self.langmuir_k = value;
+        self
+    }
+
+    pub fn with_max_uptake_g_per_g(mut self, value: f64) -> Self {
+        self.max_uptake_g_per_g = value;
+        self
+    }
+
+    pub fn with_mass_transfer_coeff(mut self, value: f64) -> Self {
+        self.mass_transfer_coeff = value;
+        self
+    }
+
+    /// Representative placeholder properties for a framework class
+    ///
+    /// `fips` is 0, as for `Default`; set it with `with_fips`. Values are
+    /// literature-typical for the class, not measurements of any one MOF.
+    pub fn default_for_class(class: MofClass) -> Self {
+        match class {
+            MofClass::Zirconium => MOFThermalProperties {
+                fips: 0,
+                thermal_conductivity: 0.35,
+                specific_heat: 800.0,
+                density: 1100.0,
+                thermal_stability_k: 773.0,
+                heat_of_adsorption: 50.0,
+                langmuir_k: 8.0,
+                max_uptake_g_per_g: 0.28,
+                mass_transfer_coeff: 0.01,
+            },
+            MofClass::Aluminum => MOFThermalProperties {
+                fips: 0,
+                thermal_conductivity: 0.45,
+                specific_heat: 950.0,
+                density: 700.0,
+                thermal_stability_k: 673.0,
+                heat_of_adsorption: 48.0,
+                langmuir_k: 6.0,
+                max_uptake_g_per_g: 0.45,
+                mass_transfer_coeff: 0.015,
+            },
+            MofClass::Copper => MOFThermalProperties {
+                fips: 0,
+                thermal_conductivity: 0.6,
+                specific_heat: 850.0,
+                density: 880.0,
+                thermal_stability_k: 523.0,
+                heat_of_adsorption: 55.0,
+                langmuir_k: 10.0,
+                max_uptake_g_per_g: 0.4,
+                mass_transfer_coeff: 0.01,
+            },
+        }
+    }
+}
+
+/// Metal-node family of a framework, for `MOFThermalProperties::default_for_class`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MofClass {
+    /// Zr-oxo clusters (UiO-66, MOF-801): dense and the most thermally robust
+    Zirconium,
+    /// Al-hydroxide chains (MOF-303, Al-fumarate): light, high water uptake
+    Aluminum,
+    /// Cu paddlewheels (HKUST-1): strong binding, decomposes near 250 °C
+    Copper,
+}
+
+impl Default for MOFThermalProperties {
+    /// Representative properties for a water-stable MOF
+    fn default() -> Self {
+        MOFThermalProperties {
+            fips: 0,
+            thermal_conductivity: 0.5,
+            specific_heat: 1000.0,
+            density: 600.0,
+            thermal_stability_k: 573.0,
+            heat_of_adsorption: 45.0,
+            langmuir_k: 5.0,
+            max_uptake_g_per_g: 0.3,
+            mass_transfer_coeff: 0.01,
+        }
+    }
 }
 
 /// Operating conditions for thermal-fluid simulation
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct OperatingConditions {
     pub ambient_temp_k: f64,
     pub regeneration_temp_k: f64,
     pub humidity: f64,                   // Relative humidity (0-1)
     pub pressure_atm: f64,
     pub cycle_time_seconds: f64,
+    #[serde(default = "default_bed_mass_kg")]
+    pub bed_mass_kg: f64,                // Dry MOF mass in the bed
+    #[serde(default)]
+    pub heat_recovery_fraction: f64,     // Share of heating energy recovered between cycles (0-1)
+    #[serde(default)]
+    pub capacity_decay_per_cycle: f64,   // Fractional capacity loss per cycle (0-1)
+    #[serde(default)]
+    pub altitude_m: f64,                 // Site elevation; pressure_atm is relative to its ambient
+    #[serde(default)]
+    pub condensation_temperature_k: Option<f64>, // Condenser temperature; None = ideal capture
+    #[serde(default)]
+    pub airflow_m3_per_s: Option<f64>,   // Air through the bed while adsorbing; None = unlimited
+    #[serde(default = "default_desorption_fraction")]
+    pub desorption_fraction: f64,        // Share of the working capacity regenerated (0-1]
 }
 
-/// Results from thermal-fluid dynamics simulation
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SimulationResults {
-    pub water_yield_kg: f64,
-    pub energy_consumption_kj: f64,
-    pub max_temperature_k: f64,
-    pub thermal_efficiency: f64,
-    pub risk_score: f64,                 // 0-1, lower is safer
+fn default_bed_mass_kg() -> f64 {
+    OperatingConditions::default().bed_mass_kg
 }
 
-/// Main thermal-fluid dynamics simulator
-pub struct ThermalFluidSimulator {
-    properties: MOFThermalProperties,
-    conditions: OperatingConditions,
+fn default_desorption_fraction() -> f64 {
+    1.0
 }
 
-impl ThermalFluidSimulator {
-    /// Create a new thermal-fluid simulator
-    pub fn new(
-        properties: MOFThermalProperties,
-        conditions: OperatingConditions,
+impl Default for OperatingConditions {
+    /// Typical temperate-climate conditions with a one-hour cycle
+    fn default() -> Self {
+        OperatingConditions {
+            ambient_temp_k: 298.0,
+            regeneration_temp_k: 373.0,
+            humidity: 0.4,
+            pressure_atm: 1.0,
+            cycle_time_seconds: 3600.0,
+            bed_mass_kg: 1.0,
+            heat_recovery_fraction: 0.0,
+            capacity_decay_per_cycle: 0.0,
+            altitude_m: 0.0,
+            condensation_temperature_k: None,
+            airflow_m3_per_s: None,
+            desorption_fraction: 1.0,
+        }
+    }
+}
+
+impl OperatingConditions {
+    /// Build conditions from temperatures in degrees Celsius
+    ///
+    /// Temperatures are converted to kelvin; remaining fields use defaults.
+    pub fn from_celsius(
+        ambient_temp_c: f64,
+        regeneration_temp_c: f64,
+        humidity: f64,
+        pressure_atm: f64,
+        cycle_time_seconds: f64,
     ) -> Self {
-        ThermalFluidSimulator {
-            properties,
-            conditions,
+        OperatingConditions {
+            ambient_temp_k: celsius_to_kelvin(ambient_temp_c),
+            regeneration_temp_k: celsius_to_kelvin(regeneration_temp_c),
+            humidity,
+            pressure_atm,
+            cycle_time_seconds,
+            ..Default::default()
         }
     }
 
-    /// Run temperature-swing adsorption simulation
-    pub fn simulate_temperature_swing(&self) -> Result<SimulationResults, Box<dyn Error>> {
-        // Temperature differential for desorption
-        let delta_t = self.conditions.regeneration_temp_k - self.conditions.ambient_temp_k;
+    /// Build conditions from unit-tagged quantities
+    ///
+    /// Typed counterpart of constructing the struct directly; remaining fields
+    /// use defaults.
+    pub fn from_units(
+        ambient_temp: Kelvin,
+        regeneration_temp: Kelvin,
+        humidity: f64,
+        pressure: Atm,
+        cycle_time_seconds: f64,
+        bed_mass: Kilograms,
+    ) -> Self {
+        OperatingConditions {
+            ambient_temp_k: ambient_temp.value(),
+            regeneration_temp_k: regeneration_temp.value(),
+            humidity,
+            pressure_atm: pressure.value(),
+            cycle_time_seconds,
+            bed_mass_kg: bed_mass.value(),
+            ..Default::default()
+        }
+    }
 
-        // Energy required for heating (simplified model)
-        // E = m * c_p * ΔT + Q_ads (heat of adsorption)
-        let heating_energy = self.properties.specific_heat * delta_t
-            + self.properties.heat_of_adsorption;
+    /// Ambient temperature as a typed quantity
+    pub fn ambient_temp(&self) -> Kelvin {
+        Kelvin(self.ambient_temp_k)
+    }
 
-        // Water yield estimation (simplified - will be expanded)
-        // Based on Langmuir isotherm capacity at given humidity
-        let max_capacity = 0.3; // g/g MOF (will be calculated from properties)
-        let uptake_fraction = self.estimate_uptake_fraction();
-        let water_yield = max_capacity * uptake_fraction;
+    /// Regeneration temperature as a typed quantity
+    pub fn regeneration_temp(&self) -> Kelvin {
+        Kelvin(self.regeneration_temp_k)
+    }
 
-        // Thermal efficiency: water produced / energy consumed
-        let efficiency = if heating_energy > 0.0 {
-            water_yield / heating_energy
-        } else {
-            0.0
-        };
+    /// Operating pressure as a typed quantity
+    pub fn pressure(&self) -> Atm {
+        Atm(self.pressure_atm)
+    }
 
-        // Risk assessment based on thermal stability
-        let risk_score = self.calculate_risk_score();
+    /// Dry MOF bed mass as a typed quantity
+    pub fn bed_mass(&self) -> Kilograms {
+        Kilograms(self.bed_mass_kg)
+    }
 
-        Ok(SimulationResults {
-            water_yield_kg: water_yield,
-            energy_consumption_kj: heating_energy,
-            max_temperature_k: self.conditions.regeneration_temp_k,
-            thermal_efficiency: efficiency,
-            risk_score,
-        })
+    /// Absolute bed pressure (atm) after the site's altitude correction
+    ///
+    /// `pressure_atm` is taken relative to ambient at `altitude_m`, so the
+    /// same setting gives a lower absolute pressure at elevation.
+    pub fn absolute_pressure_atm(&self) -> f64 {
+        self.pressure_atm * barometric_pressure_atm(self.altitude_m)
     }
 
-    /// Estimate water uptake fraction from humidity
-    fn estimate_uptake_fraction(&self) -> f64 {
-        // Simplified Langmuir model
-        // q/q_max = K * P / (1 + K * P)
-        let k = 5.0; // Adsorption constant (will be property-based)
-        let p = self.conditions.humidity;
+    /// Start a builder seeded with the default conditions
+    pub fn builder() -> OperatingConditionsBuilder {
+        OperatingConditionsBuilder::new()
+    }
 
-        (k * p) / (1.0 + k * p)
+    /// Cap `regeneration_temp_k` at `thermal_stability_k * safety_margin`
+    ///
+    /// Returns `true` if the temperature was lowered.
+    pub fn clamp_to_safe(
+        &mut self,
+        properties: &MOFThermalProperties,
+        safety_margin: f64,
+    ) -> bool {
+        let limit = properties.thermal_stability_k * safety_margin;
+        if self.regeneration_temp_k > limit {
+            self.regeneration_temp_k = limit;
+            true
+        } else {
+            false
+        }
     }
 
-    /// Calculate thermal risk score
-    fn calculate_risk_score(&self) -> f64 {
-        // Risk increases as operating temp approaches thermal stability limit
-        let temp_ratio = self.conditions.regeneration_temp_k / self.properties.thermal_stability_k;
+    /// Check physical constraints, reporting every violation at once
+    pub fn validate(&self) -> Result<(), ThermalError> {
+        let mut violations = Vec::new();
+
+        // NaN fails `is_positive`, so NaN inputs are rejected too
+        if !is_positive(self.ambient_temp_k) {
+            violations.push(format!(
+                "ambient_temp_k must be positive (got {})",
+                self.ambient_temp_k
+            ));
+        }
+        if !is_positive(self.regeneration_temp_k) {
+            violations.push(format!(
+                "regeneration_temp_k must be positive (got {})",
+                self.regeneration_temp_k
+            ));
+        }
+        if !is_positive(self.regeneration_temp_k - self.ambient_temp_k) {
+            violations.push(format!(
+                "regeneration_temp_k ({}) must exceed ambient_temp_k ({})",
+                self.regeneration_temp_k, self.ambient_temp_k
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.humidity) {
+            violations.push(format!("humidity must be within [0, 1] (got {})", self.humidity));
+        }
+        if !is_positive(self.pressure_atm) {
+            violations.push(format!("pressure_atm must be positive (got {})", self.pressure_atm));
+        }
+        if !is_positive(self.cycle_time_seconds) {
+            violations.push(format!(
+                "cycle_time_seconds must be positive (got {})",
+                self.cycle_time_seconds
+            ));
+        }
+        if !is_positive(self.bed_mass_kg) {
+            violations.push(format!("bed_mass_kg must be positive (got {})", self.bed_mass_kg));
+        }
+        if !(0.0..=1.0).contains(&self.heat_recovery_fraction) {
+            violations.push(format!(
+                "heat_recovery_fraction must be within [0, 1] (got {})",
+                self.heat_recovery_fraction
+            ));
+        }
+        if !(MIN_ALTITUDE_M..=MAX_ALTITUDE_M).contains(&self.altitude_m) {
+            violations.push(format!(
+                "altitude_m must be within [{}, {}] (got {})",
+                MIN_ALTITUDE_M, MAX_ALTITUDE_M, self.altitude_m
+            ));
+        }
+        if let Some(condenser_k) = self.condensation_temperature_k {
+            if !(is_positive(condenser_k) && condenser_k.is_finite()) {
+                violations.push(format!(
+                    "condensation_temperature_k must be positive and finite (got {})",
+                    condenser_k
+                ));
+            }
+        }
+        if !(is_positive(self.desorption_fraction) && self.desorption_fraction <= 1.0) {
+            violations.push(format!(
+                "desorption_fraction must be within (0, 1] (got {})",
+                self.desorption_fraction
+            ));
+        }
+        if let Some(airflow) = self.airflow_m3_per_s {
+            if !(is_positive(airflow) && airflow.is_finite()) {
+                violations.push(format!(
+                    "airflow_m3_per_s must be positive and finite (got {})",
+                    airflow
+                ));
+            }
+        }
+        if !(0.0..1.0).contains(&self.capacity_decay_per_cycle) {
+            violations.push(format!(
+                "capacity_decay_per_cycle must be within [0, 1) (got {})",
+                self.capacity_decay_per_cycle
+            ));
+        }
+        // Infinity passes the positivity checks above but breaks the model
+        let unbounded = [
+            ("ambient_temp_k", self.ambient_temp_k),
+            ("regeneration_temp_k", self.regeneration_temp_k),
+            ("pressure_atm", self.pressure_atm),
+            ("cycle_time_seconds", self.cycle_time_seconds),
+            ("bed_mass_kg", self.bed_mass_kg),
+        ];
+        for (name, value) in unbounded {
+            if value.is_infinite() {
+                violations.push(format!("{} must be finite (got {})", name, value));
+            }
+        }
 
-        // Safe operation: temp_ratio < 0.8
-        // Moderate risk: 0.8 - 0.9
-        // High risk: > 0.9
-        if temp_ratio < 0.8 {
-            0.1 // Low risk
-        } else if temp_ratio < 0.9 {
-            0.5 // Moderate risk
+        if violations.is_empty() {
+            Ok(())
         } else {
-            0.9 // High risk
+            Err(ThermalError::InvalidConditions(violations.join("; ")))
         }
     }
+}
 
-    /// Optimize operating conditions for maximum efficiency
-    pub fn optimize_conditions(&mut self) -> Result<OperatingConditions, Box<dyn Error>> {
-        // Find optimal regeneration temperature
-        // Balance between water yield and energy consumption
+/// Tuning parameters for the regeneration temperature sweep
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OptimizationConfig {
+    pub temp_step_k: f64,                // Sweep resolution (K)
+    pub safety_margin: f64,              // Upper bound as a fraction of thermal_stability_k
+    pub max_risk: f64,                   // Candidates must score strictly below this risk
+}
 
-        let mut best_efficiency = 0.0;
-        let mut best_temp = self.conditions.regeneration_temp_k;
+impl Default for OptimizationConfig {
+    fn default() -> Self {
+        OptimizationConfig {
+            temp_step_k: 10.0,
+            safety_margin: 0.85,
+            max_risk: 0.5,
+        }
+    }
+}
 
-        // Sweep regeneration temperatures
-        let min_temp = self.conditions.ambient_temp_k + 30.0;
-        let max_temp = self.properties.thermal_stability_k * 0.85; // Safety margin
+impl OptimizationConfig {
+    /// Check the sweep parameters are usable
+    pub fn validate(&self) -> Result<(), ThermalError> {
+        let mut violations = Vec::new();
 
-        for temp in (min_temp as i32..max_temp as i32).step_by(10) {
-            self.conditions.regeneration_temp_k = temp as f64;
+        if !is_positive(self.temp_step_k) {
+            violations.push(format!("temp_step_k must be positive (got {})", self.temp_step_k));
+        }
+        if !(is_positive(self.safety_margin) && self.safety_margin <= 1.0) {
+            violations.push(format!(
+                "safety_margin must be within (0, 1] (got {})",
+                self.safety_margin
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.max_risk) {
+            violations.push(format!("max_risk must be within [0, 1] (got {})", self.max_risk));
+        }
 
-            if let Ok(results) = self.simulate_temperature_swing() {
-                if results.thermal_efficiency > best_efficiency && results.risk_score < 0.5 {
-                    best_efficiency = results.thermal_efficiency;
-                    best_temp = temp as f64;
-                }
-            }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ThermalError::InvalidParameter(violations.join("; ")))
         }
+    }
+}
 
-        self.conditions.regeneration_temp_k = best_temp;
-        Ok(self.conditions.clone())
+/// Outcome of a regeneration temperature sweep, for diagnostics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizationReport {
+    pub conditions: OperatingConditions, // Conditions at the chosen temperature
+    pub best: SimulationResults,         // Simulation at the chosen temperature
+    pub evaluations: usize,              // Candidate temperatures simulated
+}
+
+/// Goal for `optimize_with_objective`: higher scores win among feasible points
+///
+/// The sweep's `max_risk` still applies on top of `feasible`.
+pub trait ObjectiveFn {
+    /// Figure of merit for one candidate; higher is better
+    fn score(&self, results: &SimulationResults) -> f64;
+
+    /// Whether the candidate may be selected at all
+    fn feasible(&self, results: &SimulationResults) -> bool;
+}
+
+/// Highest `thermal_efficiency`, the goal of `optimize_conditions`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MaximizeEfficiency;
+
+impl ObjectiveFn for MaximizeEfficiency {
+    fn score(&self, results: &SimulationResults) -> f64 {
+        results.thermal_efficiency
+    }
+
+    fn feasible(&self, results: &SimulationResults) -> bool {
+        results.thermal_efficiency > 0.0
     }
 }
 
-/// Load MOF properties from CSV data
-pub fn load_mof_properties(fips: u32) -> Result<MOFThermalProperties, Box<dyn Error>> {
-    // Placeholder: In production, this would read from CSV
-    // For now, return default properties
-    Ok(MOFThermalProperties {
-        fips,
-        thermal_conductivity: 0.5,
-        specific_heat: 1000.0,
-        density: 600.0,
-        thermal_stability_k: 573.0,
-        heat_of_adsorption: 45.0,
-    })
+/// Most water per cycle, whatever the energy cost
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MaximizeYield;
+
+impl ObjectiveFn for MaximizeYield {
+    fn score(&self, results: &SimulationResults) -> f64 {
+        results.water_yield_kg
+    }
+
+    fn feasible(&self, results: &SimulationResults) -> bool {
+        results.water_yield_kg > 0.0
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Least energy per cycle among points that produce any water
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MinimizeEnergy;
 
-    #[test]
-    fn test_thermal_simulation() {
-        let properties = MOFThermalProperties {
-            fips: 1005,
-            thermal_conductivity: 0.5,
-            specific_heat: 1000.0,
-            density: 600.0,
-            thermal_stability_k: 573.0,
-            heat_of_adsorption: 45.0,
-        };
+impl ObjectiveFn for MinimizeEnergy {
+    fn score(&self, results: &SimulationResults) -> f64 {
+        -results.energy_consumption_kj
+    }
 
-        let conditions = OperatingConditions {
-            ambient_temp_k: 298.0,
-            regeneration_temp_k: 373.0,
-            humidity: 0.4,
-            pressure_atm: 1.0,
-            cycle_time_seconds: 3600.0,
-        };
+    fn feasible(&self, results: &SimulationResults) -> bool {
+        results.water_yield_kg > 0.0
+    }
+}
 
-        let simulator = ThermalFluidSimulator::new(properties, conditions);
-        let results = simulator.simulate_temperature_swing().unwrap();
+/// Progress callback for long sweeps, called with `(completed, total)`
+///
+/// Invoked after every evaluation, possibly from rayon worker threads.
+pub type Progress<'a> = &'a (dyn Fn(usize, usize) + Sync);
 
-        assert!(results.water_yield_kg > 0.0);
-        assert!(results.thermal_efficiency > 0.0);
-        assert!(results.risk_score < 0.5);
+/// Fluent builder for `OperatingConditions`
+///
+/// Unset parameters keep the `OperatingConditions::default()` values, and
+/// `build()` applies the same validation as `OperatingConditions::validate`.
+#[derive(Debug, Clone, Default)]
+pub struct OperatingConditionsBuilder {
+    conditions: OperatingConditions,
+}
+
+impl OperatingConditionsBuilder {
+    /// Create a builder with default conditions
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    #[test]
-    fn test_optimization() {
-        let properties = load_mof_properties(1005).unwrap();
-        let conditions = OperatingConditions {
-            ambient_temp_k: 298.0,
-            regeneration_temp_k: 373.0,
-            humidity: 0.4,
-            pressure_atm: 1.0,
-            cycle_time_seconds: 3600.0,
-        };
+    pub fn ambient_temp_k(mut self, value: f64) -> Self {
+        self.conditions.ambient_temp_k = value;
+        self
+    }
 
-        let mut simulator = ThermalFluidSimulator::new(properties, conditions);
-        let optimized = simulator.optimize_conditions().unwrap();
+    pub fn regeneration_temp_k(mut self, value: f64) -> Self {
+        self.conditions.regeneration_temp_k = value;
+        self
+    }
 
-        assert!(optimized.regeneration_temp_k > 298.0);
-        assert!(optimized.regeneration_temp_k < 573.0 * 0.85);
+    pub fn humidity(mut self, value: f64) -> Self {
+        self.conditions.humidity = value;
+        self
+    }
+
+    pub fn pressure_atm(mut self, value: f64) -> Self {
+        self.conditions.pressure_atm = value;
+        self
+    }
+
+    pub fn cycle_time_seconds(mut self, value: f64) -> Self {
+        self.conditions.cycle_time_seconds = value;
+        self
+    }
+
+    pub fn bed_mass_kg(mut self, value: f64) -> Self {
+        self.conditions.bed_mass_kg = value;
+        self
+    }
+
+    /// Typed form of `ambient_temp_k`
+    pub fn ambient_temp(self, value: Kelvin) -> Self {
+        self.ambient_temp_k(value.value())
+    }
+
+    /// Typed form of `regeneration_temp_k`
+    pub fn regeneration_temp(self, value: Kelvin) -> Self {
+        self.regeneration_temp_k(value.value())
+    }
+
+    /// Typed form of `pressure_atm`
+    pub fn pressure(self, value: Atm) -> Self {
+        self.pressure_atm(value.value())
+    }
+
+    /// Typed form of `bed_mass_kg`
+    pub fn bed_mass(self, value: Kilograms) -> Self {
+        self.bed_mass_kg(value.value())
+    }
+
+    pub fn heat_recovery_fraction(mut self, value: f64) -> Self {
+        self.conditions.heat_recovery_fraction = value;
+        self
+    }
+
+    pub fn capacity_decay_per_cycle(mut self, value: f64) -> Self {
+        self.conditions.capacity_decay_per_cycle = value;
+        self
+    }
+
+    pub fn altitude_m(mut self, value: f64) -> Self {
+        self.conditions.altitude_m = value;
+        self
+    }
+
+    pub fn condensation_temperature_k(mut self, value: f64) -> Self {
+        self.conditions.condensation_temperature_k = Some(value);
+        self
+    }
+
+    pub fn airflow_m3_per_s(mut self, value: f64) -> Self {
+        self.conditions.airflow_m3_per_s = Some(value);
+        self
+    }
+
+    pub fn desorption_fraction(mut self, value: f64) -> Self {
+        self.conditions.desorption_fraction = value;
+        self
+    }
+
+    /// Validate and return the configured conditions
+    pub fn build(self) -> Result<OperatingConditions, ThermalError> {
+        self.conditions.validate()?;
+        Ok(self.conditions)
+    }
+}
+
+/// Offset between the Celsius and Kelvin scales
+const KELVIN_OFFSET: f64 = 273.15;
+
+/// Convert a temperature from degrees Celsius to kelvin
+pub fn celsius_to_kelvin(celsius: f64) -> f64 {
+    celsius + KELVIN_OFFSET
+}
+
+/// Convert a temperature from kelvin to degrees Celsius
+pub fn kelvin_to_celsius(kelvin: f64) -> f64 {
+    kelvin - KELVIN_OFFSET
+}
+
+/// Lowest site elevation accepted (m), below the Dead Sea shore
+const MIN_ALTITUDE_M: f64 = -500.0;
+
+/// Highest site elevation accepted (m), the top of the standard troposphere
+const MAX_ALTITUDE_M: f64 = 11_000.0;
+
+/// Ambient pressure (atm) at `altitude_m` in the standard atmosphere
+///
+/// Troposphere barometric formula: P/P₀ = (1 − L·h/T₀)^(g·M/(R·L)), with
+/// lapse rate L = 0.0065 K/m and T₀ = 288.15 K, about 0.78 atm at 2000 m.
+pub fn barometric_pressure_atm(altitude_m: f64) -> f64 {
+    const LAPSE_RATE_K_PER_M: f64 = 0.0065;
+    const SEA_LEVEL_TEMP_K: f64 = 288.15;
+    const EXPONENT: f64 = 5.25588; // g·M_air / (R·L)
+    (1.0 - LAPSE_RATE_K_PER_M * altitude_m / SEA_LEVEL_TEMP_K).powf(EXPONENT)
+}
+
+/// Pascals per millimetre of mercury
+const PA_PER_MMHG: f64 = 133.322;
+
+/// Saturation vapor pressure of water (Pa) from the Antoine equation
+///
+/// Uses the standard constants for 1-100 °C and 99-374 °C; values outside
+/// that range are extrapolated.
+pub fn saturation_pressure(temp_k: f64) -> f64 {
+    let temp_c = kelvin_to_celsius(temp_k);
+    let (a, b, c) = if temp_c < 100.0 {
+        (8.07131, 1730.63, 233.426)
+    } else {
+        (8.14019, 1810.94, 244.485)
+    };
+    10f64.powf(a - b / (c + temp_c)) * PA_PER_MMHG
+}
+
+/// Absolute humidity (g/m³) of air at relative humidity `rh`
+///
+/// Feed air at `rh` and `temp_k` compressed to `pressure_atm` carries vapor
+/// at p_v = RH · P_sat(T) · P; the ideal-gas law gives ρ_v = p_v · M / (R · T).
+/// At fixed RH, warmer air holds more water.
+pub fn relative_to_absolute_humidity(rh: f64, temp_k: f64, pressure_atm: f64) -> f64 {
+    let vapor_pressure_pa = rh * saturation_pressure(temp_k) * pressure_atm;
+    vapor_pressure_pa * WATER_MOLAR_MASS_G / (R_J_PER_MOL_K * temp_k)
+}
+
+/// Relative humidity of air holding `absolute_g_per_m3` of water vapor
+///
+/// Inverse of `relative_to_absolute_humidity`; values above 1 mean the
+/// vapor would condense at that temperature.
+pub fn absolute_to_relative_humidity(
+    absolute_g_per_m3: f64,
+    temp_k: f64,
+    pressure_atm: f64,
+) -> f64 {
+    let vapor_pressure_pa = absolute_g_per_m3 * R_J_PER_MOL_K * temp_k / WATER_MOLAR_MASS_G;
+    vapor_pressure_pa / (saturation_pressure(temp_k) * pressure_atm)
+}
+
+/// Strictly positive check that treats NaN as a failure
+fn is_positive(value: f64) -> bool {
+    value > 0.0
+}
+
+/// Schema version written to `SimulationResults::schema_version`
+///
+/// Migration notes:
+/// - 0: original fields only; implied when `schema_version` is absent
+/// - 1: adds the energy breakdown, COP, stability margin and `schema_version`
+/// - 2: adds `energy_per_liter_kwh`
+/// - 3: adds `cooling_time_seconds` and `insufficient_cycle_time`
+/// - 4: adds `uptake_efficiency` and `mass_transfer_limited`; older results
+///   assumed instantaneous uptake, so `uptake_efficiency` defaults to 1
+/// - 5: adds `efficiency_mode`; older results are `WaterPerKj`
+/// - 6: adds `lumped_model_invalid`
+/// - 7: adds `degraded`
+/// - 8: adds `condensed_fraction`; older results are 1.0 (all vapor captured)
+///
+/// Bump this when fields are added or change meaning, and mark new fields
+/// `#[serde(default)]` so JSON from older pipeline versions still loads.
+pub const CURRENT_SCHEMA_VERSION: u32 = 8;
+
+/// Results from thermal-fluid dynamics simulation
+///
+/// Field names form the JSON contract with the Python pipeline; keep them stable.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SimulationResults {
+    pub water_yield_kg: f64,
+    #[serde(default = "default_uptake_efficiency")]
+    pub uptake_efficiency: f64,          // Fraction of equilibrium working capacity reached
+    #[serde(default)]
+    pub mass_transfer_limited: bool,     // Adsorption half-cycle too short for equilibrium
+    pub energy_consumption_kj: f64,      // sensible + adsorption - recovered
+    #[serde(default)]
+    pub sensible_energy_kj: f64,         // Heating the MOF bed
+    #[serde(default)]
+    pub adsorption_energy_kj: f64,       // Heat of adsorption for the water desorbed
+    #[serde(default)]
+    pub recovered_energy_kj: f64,        // Heat recovered from the previous cycle
+    pub max_temperature_k: f64,
+    #[serde(default)]
+    pub stability_margin_k: f64,         // thermal_stability_k - max_temperature_k
+    #[serde(default)]
+    pub cooling_time_seconds: f64,       // Time to cool from peak to within 1 K of ambient
+    #[serde(default)]
+    pub insufficient_cycle_time: bool,   // Cooling does not fit in the second half-cycle
+    #[serde(default)]
+    pub lumped_model_invalid: bool,      // Biot number above 0.1; internal gradients matter
+    #[serde(default)]
+    pub degraded: bool,                  // Regenerated at or above the stability limit
+    #[serde(default = "default_condensed_fraction")]
+    pub condensed_fraction: f64,         // Share of desorbed vapor the condenser recovers
+    pub thermal_efficiency: f64,         // Normalized per efficiency_mode
+    #[serde(default)]
+    pub efficiency_mode: EfficiencyMode,
+    #[serde(default)]
+    pub coefficient_of_performance: f64, // Latent heat of captured water / input energy
+    #[serde(default, deserialize_with = "deserialize_null_as_infinity")]
+    pub energy_per_liter_kwh: f64,       // Input energy per liter of water; JSON null if infinite
+    pub risk_score: f64,                 // 0-1, lower is safer
+    #[serde(default)]
+    pub schema_version: u32,             // See CURRENT_SCHEMA_VERSION
+}
+
+fn default_condensed_fraction() -> f64 {
+    1.0
+}
+
+impl SimulationResults {
+    /// Peak bed temperature in degrees Celsius, for display
+    pub fn max_temperature_c(&self) -> f64 {
+        kelvin_to_celsius(self.max_temperature_k)
+    }
+
+    /// Water yield per cycle as a typed quantity
+    pub fn water_yield(&self) -> Kilograms {
+        Kilograms(self.water_yield_kg)
+    }
+
+    /// Net energy per cycle as a typed quantity
+    pub fn energy_consumption(&self) -> KiloJoules {
+        KiloJoules(self.energy_consumption_kj)
+    }
+
+    /// Peak bed temperature as a typed quantity
+    pub fn max_temperature(&self) -> Kelvin {
+        Kelvin(self.max_temperature_k)
+    }
+
+    /// Share of input energy carried out as latent heat of the captured water
+    ///
+    /// (latent heat × `water_yield_kg`) / `energy_consumption_kj`, i.e. the
+    /// COP clamped to [0, 1]; the rest goes to heating the bed and the heat of
+    /// adsorption beyond the latent heat. Zero when no energy is consumed.
+    pub fn latent_fraction(&self) -> f64 {
+        self.coefficient_of_performance.clamp(0.0, 1.0)
+    }
+
+    /// Specific energy consumption (kWh/m³), as water-harvesting studies report
+    ///
+    /// Input energy per cubic meter of water, taking 1000 kg ≈ 1 m³; infinite
+    /// when no water is produced.
+    pub fn specific_energy_consumption_kwh_per_m3(&self) -> f64 {
+        if self.water_yield_kg > 0.0 {
+            self.energy_consumption_kj / KJ_PER_KWH / (self.water_yield_kg / KG_WATER_PER_M3)
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    /// Pareto dominance on yield (higher), energy (lower) and risk (lower)
+    ///
+    /// True when `self` is at least as good as `other` on all three and
+    /// strictly better on at least one.
+    pub fn dominates(&self, other: &SimulationResults) -> bool {
+        let no_worse = self.water_yield_kg >= other.water_yield_kg
+            && self.energy_consumption_kj <= other.energy_consumption_kj
+            && self.risk_score <= other.risk_score;
+        let better = self.water_yield_kg > other.water_yield_kg
+            || self.energy_consumption_kj < other.energy_consumption_kj
+            || self.risk_score < other.risk_score;
+        no_worse && better
+    }
+
+    /// Percent differences from `baseline`, for "X% better" reporting
+    ///
+    /// Each entry is (self − baseline) / |baseline| × 100, so a positive
+    /// energy or risk change is worse and a positive yield or efficiency
+    /// change is better. Fields where the baseline is zero are `None`.
+    pub fn compare_to_baseline(&self, baseline: &SimulationResults) -> ComparisonReport {
+        let percent = |value: f64, base: f64| {
+            (base != 0.0).then(|| (value - base) / base.abs() * 100.0)
+        };
+        ComparisonReport {
+            water_yield_pct: percent(self.water_yield_kg, baseline.water_yield_kg),
+            energy_consumption_pct: percent(
+                self.energy_consumption_kj,
+                baseline.energy_consumption_kj,
+            ),
+            thermal_efficiency_pct: percent(self.thermal_efficiency, baseline.thermal_efficiency),
+            risk_score_pct: percent(self.risk_score, baseline.risk_score),
+        }
+    }
+
+    /// Pretty-printed JSON for logging or hand-off to the Python pipeline
+    pub fn to_json_string(&self) -> Result<String, ThermalError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Pretty-printed JSON with top-level keys in the given casing
+    ///
+    /// `KeyCase::Snake` is identical to `to_json_string`. Camel-case keys are
+    /// emitted in alphabetical rather than field order.
+    pub fn to_json_string_with_case(&self, case: KeyCase) -> Result<String, ThermalError> {
+        match case {
+            KeyCase::Snake => self.to_json_string(),
+            KeyCase::Camel => {
+                let value = rename_keys(serde_json::to_value(self)?, snake_to_camel);
+                Ok(serde_json::to_string_pretty(&value)?)
+            }
+        }
+    }
+
+    /// Parse JSON whose top-level keys use the given casing
+    pub fn from_json_str_with_case(text: &str, case: KeyCase) -> Result<Self, ThermalError> {
+        let value: serde_json::Value = serde_json::from_str(text)?;
+        let value = match case {
+            KeyCase::Snake => value,
+            KeyCase::Camel => rename_keys(value, camel_to_snake),
+        };
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Write results as pretty-printed JSON
+    pub fn to_json_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ThermalError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Read results previously written by `to_json_file` (or the Python side)
+    pub fn from_json_file<P: AsRef<Path>>(path: P) -> Result<Self, ThermalError> {
+        let reader = BufReader::new(File::open(path)?);
+        Ok(serde_json::from_reader(reader)?)
+    }
+
+    /// CSV header matching `to_csv_row`: `fips` then every field in order
+    pub fn csv_header() -> String {
+        std::iter::once("fips")
+            .chain(RESULTS_CSV_COLUMNS)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// One CSV row for the site `fips`, in `csv_header` order
+    ///
+    /// Floats use Rust's shortest round-trip formatting, so parsing the row
+    /// restores the same values; infinities are written as `inf`.
+    pub fn to_csv_row(&self, fips: u32) -> String {
+        // Destructure so a new field cannot be left out of the export
+        let SimulationResults {
+            water_yield_kg,
+            uptake_efficiency,
+            mass_transfer_limited,
+            energy_consumption_kj,
+            sensible_energy_kj,
+            adsorption_energy_kj,
+            recovered_energy_kj,
+            max_temperature_k,
+            stability_margin_k,
+            cooling_time_seconds,
+            insufficient_cycle_time,
+            lumped_model_invalid,
+            degraded,
+            condensed_fraction,
+            thermal_efficiency,
+            efficiency_mode,
+            coefficient_of_performance,
+            energy_per_liter_kwh,
+            risk_score,
+            schema_version,
+        } = self;
+        let values: [String; RESULTS_CSV_COLUMNS.len()] = [
+            water_yield_kg.to_string(),
+            uptake_efficiency.to_string(),
+            mass_transfer_limited.to_string(),
+            energy_consumption_kj.to_string(),
+            sensible_energy_kj.to_string(),
+            adsorption_energy_kj.to_string(),
+            recovered_energy_kj.to_string(),
+            max_temperature_k.to_string(),
+            stability_margin_k.to_string(),
+            cooling_time_seconds.to_string(),
+            insufficient_cycle_time.to_string(),
+            lumped_model_invalid.to_string(),
+            degraded.to_string(),
+            condensed_fraction.to_string(),
+            thermal_efficiency.to_string(),
+            format!("{:?}", efficiency_mode),
+            coefficient_of_performance.to_string(),
+            energy_per_liter_kwh.to_string(),
+            risk_score.to_string(),
+            schema_version.to_string(),
+        ];
+        std::iter::once(fips.to_string())
+            .chain(values)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// `SimulationResults` fields in CSV column order, after the leading `fips`
+const RESULTS_CSV_COLUMNS: [&str; 20] = [
+    "water_yield_kg",
+    "uptake_efficiency",
+    "mass_transfer_limited",
+    "energy_consumption_kj",
+    "sensible_energy_kj",
+    "adsorption_energy_kj",
+    "recovered_energy_kj",
+    "max_temperature_k",
+    "stability_margin_k",
+    "cooling_time_seconds",
+    "insufficient_cycle_time",
+    "lumped_model_invalid",
+    "degraded",
+    "condensed_fraction",
+    "thermal_efficiency",
+    "efficiency_mode",
+    "coefficient_of_performance",
+    "energy_per_liter_kwh",
+    "risk_score",
+    "schema_version",
+];
+
+/// Write `(fips, results)` rows to a CSV file with a `csv_header` line
+pub fn write_results_csv<P: AsRef<Path>>(
+    path: P,
+    rows: &[(u32, SimulationResults)],
+) -> Result<(), ThermalError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(writer, "{}", SimulationResults::csv_header())?;
+    for (fips, results) in rows {
+        writeln!(writer, "{}", results.to_csv_row(*fips))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// JSON key casing for `SimulationResults` at the Python boundary
+///
+/// Snake case is the default everywhere else in the crate. The keys for each
+/// field, in field order, are:
+///
+/// ```text
+/// Snake                        Camel
+/// water_yield_kg               waterYieldKg
+/// uptake_efficiency            uptakeEfficiency
+/// mass_transfer_limited        massTransferLimited
+/// energy_consumption_kj        energyConsumptionKj
+/// sensible_energy_kj           sensibleEnergyKj
+/// adsorption_energy_kj         adsorptionEnergyKj
+/// recovered_energy_kj          recoveredEnergyKj
+/// max_temperature_k            maxTemperatureK
+/// stability_margin_k           stabilityMarginK
+/// cooling_time_seconds         coolingTimeSeconds
+/// insufficient_cycle_time      insufficientCycleTime
+/// lumped_model_invalid         lumpedModelInvalid
+/// degraded                     degraded
+/// condensed_fraction           condensedFraction
+/// thermal_efficiency           thermalEfficiency
+/// efficiency_mode              efficiencyMode
+/// coefficient_of_performance   coefficientOfPerformance
+/// energy_per_liter_kwh         energyPerLiterKwh
+/// risk_score                   riskScore
+/// schema_version               schemaVersion
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyCase {
+    /// Rust field names, e.g. `water_yield_kg`
+    #[default]
+    Snake,
+    /// e.g. `waterYieldKg`, for pandas/JavaScript-style consumers
+    Camel,
+}
+
+fn snake_to_camel(key: &str) -> String {
+    let mut parts = key.split('_');
+    let mut camel = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            camel.extend(first.to_uppercase());
+            camel.push_str(chars.as_str());
+        }
+    }
+    camel
+}
+
+fn camel_to_snake(key: &str) -> String {
+    let mut snake = String::with_capacity(key.len() + 4);
+    for c in key.chars() {
+        if c.is_ascii_uppercase() {
+            snake.push('_');
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+/// Rename the keys of a JSON object, leaving nested values untouched
+fn rename_keys(value: serde_json::Value, rename: impl Fn(&str) -> String) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.into_iter().map(|(key, value)| (rename(&key), value)).collect()
+        }
+        other => other,
+    }
+}
+
+fn default_uptake_efficiency() -> f64 {
+    1.0
+}
+
+/// Read a float that JSON wrote as `null` because it was infinite
+///
+/// serde_json serializes non-finite floats as `null`; the only non-finite
+/// value a simulation produces is `f64::INFINITY`, so map it back.
+fn deserialize_null_as_infinity<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or(f64::INFINITY))
+}
+
+/// Bed counts as cooled once within this many kelvin of ambient
+const COOLING_TOLERANCE_K: f64 = 1.0;
+
+/// Uptake efficiency below which adsorption counts as mass-transfer limited
+const EQUILIBRIUM_UPTAKE_EFFICIENCY: f64 = 0.99;
+
+/// Temperature at which `max_uptake_g_per_g` is the saturation capacity (K)
+const CAPACITY_REFERENCE_TEMP_K: f64 = 298.15;
+
+/// Seconds in a day, for daily production totals
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// kJ per kWh
+const KJ_PER_KWH: f64 = 3600.0;
+
+/// Temperature ratio (T_regen / T_stability) below which operation is safe
+const SAFE_TEMP_RATIO: f64 = 0.8;
+
+/// Risk score reached at the edge of the safe region
+const SAFE_RISK_CEILING: f64 = 0.1;
+
+/// Specific heat of the adsorbed phase, taken as liquid water (J/(kg·K))
+const ADSORBED_PHASE_SPECIFIC_HEAT: f64 = 4186.0;
+
+/// Convergence tolerance for golden-section temperature search (K)
+const GOLDEN_TOLERANCE_K: f64 = 0.5;
+
+/// Mass of one cubic meter of liquid water (kg), as 1 kg ≈ 1 L
+const KG_WATER_PER_M3: f64 = 1000.0;
+
+/// Relative tolerance for `validate_energy_balance`
+const ENERGY_BALANCE_TOLERANCE: f64 = 1e-9;
+
+/// Relative shortfall tolerated when checking a sized bed against its target
+const SIZING_TOLERANCE: f64 = 1e-9;
+
+/// Largest Biot number for which the lumped-capacitance model holds
+const MAX_LUMPED_BIOT_NUMBER: f64 = 0.1;
+
+/// Bisection tolerance for `temperature_for_desorption_fraction` (K)
+const DESORPTION_TOLERANCE_K: f64 = 1e-6;
+
+/// Iteration cap for golden-section search, well beyond normal convergence
+const MAX_GOLDEN_ITERATIONS: usize = 100;
+
+/// Default risk threshold for `simulate_temperature_swing_safe`
+const DEFAULT_MAX_ALLOWED_RISK: f64 = 0.5;
+
+/// Characteristic heat conduction length of the MOF bed (m)
+const BED_CHARACTERISTIC_LENGTH_M: f64 = 0.01;
+
+/// How `SimulationResults::thermal_efficiency` is normalized
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum EfficiencyMode {
+    /// Water produced per unit input energy (kg/kJ)
+    #[default]
+    WaterPerKj,
+    /// Latent heat carried by the captured water per unit input energy
+    Dimensionless,
+    /// Carnot-limited minimum desorption energy over the actual input energy
+    ///
+    /// The minimum is the heat of desorbing the water, Q_des, weighted by the
+    /// Carnot factor (1 − T_amb / T_peak) between the bed peak and ambient.
+    SecondLaw,
+}
+
+/// What `simulate_temperature_swing` does when `regeneration_temp_k` reaches
+/// `thermal_stability_k`
+///
+/// Heating the framework to its stability limit destroys it, so the cycle's
+/// nominal yield is meaningless. The boundary itself counts as exceeded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StabilityPolicy {
+    /// Report zero yield, `risk_score = 1.0` and `degraded = true`; the
+    /// heating energy is still charged
+    #[default]
+    Degrade,
+    /// Fail with `ThermalError::StabilityExceeded`
+    Error,
+}
+
+/// Sorbate cycled through the bed
+///
+/// Sets the molar mass used to charge the heat of adsorption per kilogram
+/// desorbed and the latent heat credited in COP and `Dimensionless`
+/// efficiency. The vapor-pressure and humidity models remain water's.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WorkingFluid {
+    pub molar_mass_g_per_mol: f64,
+    pub latent_heat_kj_per_kg: f64,      // Heat of vaporization
+}
+
+impl WorkingFluid {
+    /// Water, the default sorbate
+    pub fn water() -> Self {
+        WorkingFluid {
+            molar_mass_g_per_mol: WATER_MOLAR_MASS_G,
+            latent_heat_kj_per_kg: LATENT_HEAT_WATER_KJ_KG,
+        }
+    }
+
+    /// Methanol, latent heat at its normal boiling point
+    pub fn methanol() -> Self {
+        WorkingFluid {
+            molar_mass_g_per_mol: 32.04,
+            latent_heat_kj_per_kg: 1100.0,
+        }
+    }
+
+    /// Check that both constants are positive and finite
+    pub fn validate(&self) -> Result<(), ThermalError> {
+        for (name, value) in [
+            ("molar_mass_g_per_mol", self.molar_mass_g_per_mol),
+            ("latent_heat_kj_per_kg", self.latent_heat_kj_per_kg),
+        ] {
+            if !(is_positive(value) && value.is_finite()) {
+                return Err(ThermalError::InvalidParameter(format!(
+                    "working fluid {} must be positive and finite (got {})",
+                    name, value
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for WorkingFluid {
+    fn default() -> Self {
+        WorkingFluid::water()
+    }
+}
+
+/// Counterflow exchanger between the hot desorption stream and cold inlet air
+///
+/// ε-NTU form: the recovered heat is `effectiveness` times the most the
+/// stream could give up cooling from its inlet to the cold inlet temperature.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HeatExchanger {
+    pub effectiveness: f64,              // 0 = no exchange, 1 = ideal
+}
+
+impl HeatExchanger {
+    /// Create an exchanger, rejecting effectiveness outside [0, 1]
+    pub fn new(effectiveness: f64) -> Result<Self, ThermalError> {
+        let exchanger = HeatExchanger { effectiveness };
+        exchanger.validate()?;
+        Ok(exchanger)
+    }
+
+    /// Check that `effectiveness` lies in [0, 1]
+    pub fn validate(&self) -> Result<(), ThermalError> {
+        if !(0.0..=1.0).contains(&self.effectiveness) {
+            return Err(ThermalError::InvalidParameter(format!(
+                "heat exchanger effectiveness must be in [0, 1] (got {})",
+                self.effectiveness
+            )));
+        }
+        Ok(())
+    }
+
+    /// Heat recovered from a hot stream entering at `hot_inlet_k` (kJ)
+    ///
+    /// `heat_capacity_kj_per_k` is the hot stream's total heat capacity; the
+    /// cold inlet at `cold_inlet_k` bounds how far it can cool. No heat flows
+    /// when the hot stream is not hotter.
+    pub fn recovered_energy_kj(
+        &self,
+        hot_inlet_k: f64,
+        cold_inlet_k: f64,
+        heat_capacity_kj_per_k: f64,
+    ) -> f64 {
+        self.effectiveness * heat_capacity_kj_per_k * (hot_inlet_k - cold_inlet_k).max(0.0)
+    }
+}
+
+/// Energy-recovery ventilator preconditioning inlet air against a return stream
+///
+/// The sensible core moves the inlet temperature `sensible_effectiveness` of
+/// the way to the return air's; the latent core does the same for its water
+/// vapor content (absolute humidity). In humid climates this precools and
+/// predehumidifies the air reaching the adsorption bed.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ErvStage {
+    pub sensible_effectiveness: f64,     // 0 = no exchange, 1 = ideal
+    pub latent_effectiveness: f64,       // 0 = no exchange, 1 = ideal
+}
+
+impl ErvStage {
+    /// Create an ERV stage, rejecting effectiveness outside [0, 1]
+    pub fn new(
+        sensible_effectiveness: f64,
+        latent_effectiveness: f64,
+    ) -> Result<Self, ThermalError> {
+        let stage = ErvStage {
+            sensible_effectiveness,
+            latent_effectiveness,
+        };
+        stage.validate()?;
+        Ok(stage)
+    }
+
+    /// Check that both effectiveness values lie in [0, 1]
+    pub fn validate(&self) -> Result<(), ThermalError> {
+        for (name, value) in [
+            ("sensible", self.sensible_effectiveness),
+            ("latent", self.latent_effectiveness),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(ThermalError::InvalidParameter(format!(
+                    "ERV {} effectiveness must be in [0, 1] (got {})",
+                    name, value
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Conditions entering the bed after exchanging with return air
+    ///
+    /// The return stream is at `return_temp_k` and relative humidity
+    /// `return_humidity`, at the same pressure as the feed. The outgoing
+    /// `ambient_temp_k` and `humidity` describe the preconditioned air;
+    /// humidity is capped at 1, where precooled vapor would condense.
+    pub fn precondition(
+        &self,
+        conditions: &OperatingConditions,
+        return_temp_k: f64,
+        return_humidity: f64,
+    ) -> OperatingConditions {
+        let pressure = conditions.absolute_pressure_atm();
+        let inlet_temp = conditions.ambient_temp_k;
+        let inlet_vapor = relative_to_absolute_humidity(conditions.humidity, inlet_temp, pressure);
+        let return_vapor = relative_to_absolute_humidity(return_humidity, return_temp_k, pressure);
+
+        let temp_k = inlet_temp - self.sensible_effectiveness * (inlet_temp - return_temp_k);
+        let vapor = inlet_vapor - self.latent_effectiveness * (inlet_vapor - return_vapor);
+        OperatingConditions {
+            ambient_temp_k: temp_k,
+            humidity: absolute_to_relative_humidity(vapor, temp_k, pressure).clamp(0.0, 1.0),
+            ..conditions.clone()
+        }
+    }
+}
+
+/// Adsorption isotherm relating relative humidity to fractional uptake
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum IsothermModel {
+    /// Single-site Langmuir: q/q_max = K·p / (1 + K·p)
+    ///
+    /// `k` is the constant at `reference_temp_k`; at other bed temperatures
+    /// it follows the van 't Hoff relation (see `langmuir_k_at`).
+    Langmuir {
+        k: f64,
+        #[serde(default = "default_isotherm_reference_temp_k")]
+        reference_temp_k: f64,
+    },
+    /// Heterogeneous-site Freundlich: q/q_max = K·p^(1/n), clamped to [0, 1]
+    Freundlich { k: f64, n: f64 },
+}
+
+fn default_isotherm_reference_temp_k() -> f64 {
+    CAPACITY_REFERENCE_TEMP_K
+}
+
+impl IsothermModel {
+    /// Langmuir isotherm with `k` measured at 298.15 K
+    pub fn langmuir(k: f64) -> Self {
+        IsothermModel::Langmuir {
+            k,
+            reference_temp_k: CAPACITY_REFERENCE_TEMP_K,
+        }
+    }
+
+    /// Fractional uptake (q/q_max) at relative pressure `p`
+    ///
+    /// Evaluated with the constants as given, i.e. at the Langmuir reference
+    /// temperature. Use `uptake_fraction_at` for other bed temperatures.
+    pub fn uptake_fraction(&self, p: f64) -> f64 {
+        match *self {
+            IsothermModel::Langmuir { k, .. } => (k * p) / (1.0 + k * p),
+            IsothermModel::Freundlich { k, n } => (k * p.powf(1.0 / n)).clamp(0.0, 1.0),
+        }
+    }
+
+    /// Langmuir constant at bed temperature `temp_k`, or `None` for Freundlich
+    ///
+    /// van 't Hoff: K(T) = K_ref · exp(−ΔH/R · (1/T − 1/T_ref)), with
+    /// ΔH = −`heat_of_adsorption_kj_per_mol` the (exothermic) adsorption
+    /// enthalpy, so a positive heat of adsorption weakens binding as the bed
+    /// heats.
+    pub fn langmuir_k_at(&self, temp_k: f64, heat_of_adsorption_kj_per_mol: f64) -> Option<f64> {
+        match *self {
+            IsothermModel::Langmuir { k, reference_temp_k } => {
+                let exponent = heat_of_adsorption_kj_per_mol * J_PER_KJ
+                    / R_J_PER_MOL_K
+                    * (1.0 / temp_k - 1.0 / reference_temp_k);
+                Some(k * exponent.exp())
+            }
+            IsothermModel::Freundlich { .. } => None,
+        }
+    }
+
+    /// Fractional uptake at relative pressure `p` and bed temperature `temp_k`
+    ///
+    /// Langmuir uses `langmuir_k_at`; Freundlich constants are taken as
+    /// temperature-independent.
+    pub fn uptake_fraction_at(
+        &self,
+        p: f64,
+        temp_k: f64,
+        heat_of_adsorption_kj_per_mol: f64,
+    ) -> f64 {
+        match self.langmuir_k_at(temp_k, heat_of_adsorption_kj_per_mol) {
+            // Same as K·p / (1 + K·p), but stays finite as K overflows to ∞
+            Some(k) => p / (1.0 / k + p),
+            None => self.uptake_fraction(p),
+        }
+    }
+
+    /// Check the isotherm constants are physically meaningful
+    pub fn validate(&self) -> Result<(), ThermalError> {
+        match *self {
+            IsothermModel::Langmuir { k, .. } if !is_positive(k) => {
+                Err(ThermalError::InvalidParameter(format!(
+                    "Langmuir k must be positive (got {})",
+                    k
+                )))
+            }
+            IsothermModel::Langmuir { reference_temp_k, .. } if !is_positive(reference_temp_k) => {
+                Err(ThermalError::InvalidParameter(format!(
+                    "Langmuir reference temperature must be positive (got {})",
+                    reference_temp_k
+                )))
+            }
+            IsothermModel::Freundlich { k, n } if !(is_positive(k) && is_positive(n)) => {
+                Err(ThermalError::InvalidParameter(format!(
+                    "Freundlich k and n must be positive (got k = {}, n = {})",
+                    k, n
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Bed built from stacked MOF layers sharing one set of operating conditions
+///
+/// `mass_fractions[i]` is the share of `bed_mass_kg` held by `layers[i]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayeredBed {
+    pub layers: Vec<MOFThermalProperties>,
+    pub mass_fractions: Vec<f64>,
+}
+
+impl LayeredBed {
+    /// Bed with every layer holding an equal share of the mass
+    pub fn uniform(layers: Vec<MOFThermalProperties>) -> Self {
+        let fraction = 1.0 / layers.len() as f64;
+        let mass_fractions = vec![fraction; layers.len()];
+        LayeredBed {
+            layers,
+            mass_fractions,
+        }
+    }
+
+    /// Check there is one positive fraction per layer and they sum to 1
+    pub fn validate(&self) -> Result<(), ThermalError> {
+        let mut violations = Vec::new();
+
+        if self.layers.is_empty() {
+            violations.push("bed must have at least one layer".to_string());
+        }
+        if self.mass_fractions.len() != self.layers.len() {
+            violations.push(format!(
+                "expected {} mass fractions (got {})",
+                self.layers.len(),
+                self.mass_fractions.len()
+            ));
+        }
+        if !self.mass_fractions.iter().all(|&fraction| is_positive(fraction)) {
+            violations.push("mass fractions must be positive".to_string());
+        }
+        let total: f64 = self.mass_fractions.iter().sum();
+        if (total - 1.0).abs() > MASS_FRACTION_TOLERANCE {
+            violations.push(format!("mass fractions must sum to 1 (got {})", total));
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ThermalError::InvalidParameter(violations.join("; ")))
+        }
+    }
+}
+
+/// Allowed deviation of layer mass fractions from summing to exactly 1
+const MASS_FRACTION_TOLERANCE: f64 = 1e-6;
+
+/// Standard deviations of measured MOF properties, in each field's units
+///
+/// Zero (the default) leaves a property unperturbed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PropertyUncertainty {
+    pub thermal_conductivity: f64,
+    pub specific_heat: f64,
+    pub density: f64,
+    pub thermal_stability_k: f64,
+    pub heat_of_adsorption: f64,
+    pub langmuir_k: f64,
+    pub max_uptake_g_per_g: f64,
+}
+
+impl PropertyUncertainty {
+    /// Draw one perturbed copy of `properties`
+    ///
+    /// Each field gets independent Gaussian noise; draws are truncated at
+    /// zero since none of these properties can be negative.
+    fn sample(&self, properties: &MOFThermalProperties, rng: &mut StdRng) -> MOFThermalProperties {
+        let mut perturb = |mean: f64, std_dev: f64| {
+            let z: f64 = rng.sample(StandardNormal);
+            (mean + std_dev * z).max(0.0)
+        };
+        MOFThermalProperties {
+            thermal_conductivity: perturb(
+                properties.thermal_conductivity,
+                self.thermal_conductivity,
+            ),
+            specific_heat: perturb(properties.specific_heat, self.specific_heat),
+            density: perturb(properties.density, self.density),
+            thermal_stability_k: perturb(properties.thermal_stability_k, self.thermal_stability_k),
+            heat_of_adsorption: perturb(properties.heat_of_adsorption, self.heat_of_adsorption),
+            langmuir_k: perturb(properties.langmuir_k, self.langmuir_k),
+            max_uptake_g_per_g: perturb(properties.max_uptake_g_per_g, self.max_uptake_g_per_g),
+            ..properties.clone()
+        }
+    }
+}
+
+/// Mean, spread and percentiles of one simulated quantity
+///
+/// All fields are NaN when there were no samples.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DistributionSummary {
+    pub mean: f64,
+    pub std_dev: f64,                    // Sample standard deviation
+    pub p5: f64,
+    pub p50: f64,
+    pub p95: f64,
+}
+
+impl DistributionSummary {
+    /// Summarize values, sorting them in place
+    fn from_values(values: &mut [f64]) -> Self {
+        values.sort_by(f64::total_cmp);
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let variance = if values.len() > 1 {
+            values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+
+        DistributionSummary {
+            mean,
+            std_dev: variance.sqrt(),
+            p5: percentile(values, 0.05),
+            p50: percentile(values, 0.5),
+            p95: percentile(values, 0.95),
+        }
+    }
+}
+
+/// Linearly interpolated percentile of sorted values (`q` in [0, 1])
+fn percentile(sorted: &[f64], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let rank = q * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/// Result of a Monte Carlo property-uncertainty run
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MonteCarloSummary {
+    pub samples: usize,                  // Successful simulations summarized
+    pub water_yield_kg: DistributionSummary,
+    pub risk_score: DistributionSummary,
+}
+
+/// One weather observation driving `simulate_timeseries`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WeatherSample {
+    pub temp_k: f64,                     // Ambient air temperature (K)
+    pub humidity: f64,                   // Relative humidity (0-1)
+    pub pressure_atm: f64,               // Operating pressure (atm)
+}
+
+/// Totals accumulated over a weather time series
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AnnualSummary {
+    pub samples: usize,                  // Samples simulated successfully
+    pub failed_samples: usize,           // Samples whose simulation failed
+    pub water_yield_kg: f64,             // Total over successful samples
+    pub energy_consumption_kj: f64,      // Total over successful samples
+    pub max_risk_score: f64,             // Highest single-sample risk
+}
+
+/// Multi-cycle run with its warm-up separated out, from `simulate_cycles_summary`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CycleSummary {
+    pub cycles: Vec<SimulationResults>,
+    pub steady_state_cycle: Option<usize>, // First settled cycle; None if never settled
+    pub steady_state_yield_kg: Option<f64>, // Mean per-cycle yield from that cycle on
+}
+
+/// First cycle whose yield differs from the previous one by less than `tolerance_kg`
+///
+/// Zero-based, so at least 1; `None` if the series never settles.
+pub fn steady_state_cycle(yields_kg: &[f64], tolerance_kg: f64) -> Option<usize> {
+    yields_kg
+        .windows(2)
+        .position(|pair| (pair[1] - pair[0]).abs() < tolerance_kg)
+        .map(|i| i + 1)
+}
+
+/// Dense regeneration temperature × humidity grid from `sweep_grid`
+///
+/// Cells are row-major: row `i` is `temp_axis[i]`, column `j` is
+/// `humidity_axis[j]`. A cell is `None` where that combination failed to
+/// simulate, e.g. a setpoint at or below ambient or a humidity outside 0-1.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SweepGrid {
+    pub temp_axis: Vec<f64>,             // Regeneration temperatures (K)
+    pub humidity_axis: Vec<f64>,         // Relative humidities (0-1)
+    pub cells: Vec<Option<SimulationResults>>,
+}
+
+impl SweepGrid {
+    /// Results at `temp_axis[i]`, `humidity_axis[j]`
+    ///
+    /// `None` if the indices are out of range or the cell failed to simulate.
+    pub fn get(&self, i: usize, j: usize) -> Option<&SimulationResults> {
+        if i >= self.temp_axis.len() || j >= self.humidity_axis.len() {
+            return None;
+        }
+        self.cells[i * self.humidity_axis.len() + j].as_ref()
+    }
+}
+
+/// Risk threshold crossed by the peak bed temperature, least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RiskThreshold {
+    /// Peak exceeds 80% of `thermal_stability_k`; risk rises steeply beyond
+    SafeTempRatio,
+    /// `risk_score` exceeds the simulator's `max_allowed_risk`
+    MaxAllowedRisk,
+    /// Peak reaches `thermal_stability_k`; the framework may degrade
+    StabilityLimit,
+}
+
+/// Breakdown of the thermal risk behind `risk_score`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiskReport {
+    pub risk_score: f64,
+    pub temp_ratio: f64,                 // Peak temperature / thermal_stability_k
+    pub stability_margin_k: f64,         // thermal_stability_k - peak temperature
+    pub threshold_crossed: Option<RiskThreshold>, // Most severe threshold crossed
+    pub assessment: String,              // One-line summary for operators
+}
+
+/// What holds back a run's yield, from `ThermalFluidSimulator::limiting_factor`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LimitingFactor {
+    /// The isotherm's working capacity between ambient and the setpoint
+    EquilibriumCapacity,
+    /// Adsorption kinetics: the half-cycle ends before loading equilibrates
+    MassTransfer,
+    /// The inlet air carries less water than the bed could take up
+    Airflow,
+    /// The heating half-cycle ends before the bed reaches the setpoint
+    CycleTime,
+}
+
+/// Where one cycle's heating energy goes (kJ)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EnergyBreakdown {
+    pub sensible_kj: f64,                // Heating the MOF bed
+    pub adsorption_kj: f64,              // Heat of adsorption for the water desorbed
+    pub recovered_kj: f64,               // Heat recovered, subtracted from the total
+    pub net_kj: f64,                     // sensible + adsorption - recovered
+}
+
+/// Every metric for one run, from `ThermalFluidSimulator::full_report`
+///
+/// `Display` renders it as a short multi-line block for terminal output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FullReport {
+    pub results: SimulationResults,
+    pub risk: RiskReport,
+    pub energy: EnergyBreakdown,
+    pub daily_water_production_kg: f64,
+    pub limiting_factor: Option<LimitingFactor>, // None for a degraded run
+    pub summary: String,                 // One-line headline
+}
+
+impl fmt::Display for FullReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let results = &self.results;
+        writeln!(f, "{}", self.summary)?;
+        writeln!(
+            f,
+            "  water:      {:.4} kg/cycle, {:.2} kg/day",
+            results.water_yield_kg, self.daily_water_production_kg
+        )?;
+        writeln!(
+            f,
+            "  energy:     {:.2} kJ net = {:.2} sensible + {:.2} adsorption - {:.2} recovered",
+            self.energy.net_kj,
+            self.energy.sensible_kj,
+            self.energy.adsorption_kj,
+            self.energy.recovered_kj
+        )?;
+        writeln!(
+            f,
+            "  efficiency: {:.3e} ({:?}), COP {:.3}",
+            results.thermal_efficiency, results.efficiency_mode, results.coefficient_of_performance
+        )?;
+        writeln!(
+            f,
+            "  peak:       {:.1} K ({:.1} °C), {:.1} K below stability",
+            results.max_temperature_k,
+            results.max_temperature_c(),
+            results.stability_margin_k
+        )?;
+        match self.limiting_factor {
+            Some(factor) => writeln!(f, "  limited by: {:?}", factor)?,
+            None => writeln!(f, "  limited by: framework degraded")?,
+        }
+        write!(f, "  risk:       {}", self.risk.assessment)
+    }
+}
+
+/// Everything needed to set up a simulation, as read from a config file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationConfig {
+    pub properties: MOFThermalProperties,
+    pub conditions: OperatingConditions,
+}
+
+impl SimulationConfig {
+    /// Read a config from a `.toml` file, or JSON for any other extension
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ThermalError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => Ok(toml::from_str(&text)?),
+            _ => Ok(serde_json::from_str(&text)?),
+        }
+    }
+}
+
+/// Setpoint-independent terms of the swing model, see `SimulationContext`
+#[derive(Debug, Clone, Copy)]
+struct SwingInvariants {
+    adsorbed_g_per_g: f64,               // Equilibrium loading at ambient
+    uptake_efficiency: f64,
+    exotherm_peak_k: f64,
+    supplied_water_kg: f64,              // Vapor carried in by the airflow; infinite if unset
+}
+
+/// Validated simulator with ambient-side terms cached, for interactive sweeps
+///
+/// Uptake at ambient, the kinetic uptake efficiency and the adsorption
+/// exotherm depend only on the feed air, cycle time and material, so they are
+/// computed once by `ThermalFluidSimulator::context`. Each call then varies
+/// only the regeneration setpoint and gives the same results as a fresh
+/// `simulate_temperature_swing` at that setpoint.
+#[derive(Debug, Clone)]
+pub struct SimulationContext {
+    simulator: ThermalFluidSimulator,
+    invariants: SwingInvariants,
+}
+
+impl SimulationContext {
+    /// Simulate one cycle regenerating at `regeneration_temp_k`
+    pub fn with_regeneration_temp(
+        &self,
+        regeneration_temp_k: f64,
+    ) -> Result<SimulationResults, ThermalError> {
+        let conditions = OperatingConditions {
+            regeneration_temp_k,
+            ..self.simulator.conditions.clone()
+        };
+        conditions.validate()?;
+        self.simulator
+            .with_conditions(conditions)
+            .simulate_swing_with(&self.invariants)
+    }
+}
+
+/// Main thermal-fluid dynamics simulator
+#[derive(Debug, Clone)]
+pub struct ThermalFluidSimulator {
+    properties: MOFThermalProperties,
+    conditions: OperatingConditions,
+    isotherm: IsothermModel,
+    max_allowed_risk: f64,
+    efficiency_mode: EfficiencyMode,
+    hysteresis_factor: f64,
+    working_fluid: WorkingFluid,
+    heat_exchanger: Option<HeatExchanger>,
+    heat_transfer_coeff: Option<f64>,
+    stability_policy: StabilityPolicy,
+}
+
+impl Default for ThermalFluidSimulator {
+    /// Simulator for the default MOF at the default conditions
+    fn default() -> Self {
+        Self::new(MOFThermalProperties::default(), OperatingConditions::default())
+    }
+}
+
+impl ThermalFluidSimulator {
+    /// Create a new thermal-fluid simulator
+    ///
+    /// Uses a Langmuir isotherm with the material's `langmuir_k` at 298.15 K.
+    pub fn new(
+        properties: MOFThermalProperties,
+        conditions: OperatingConditions,
+    ) -> Self {
+        let isotherm = IsothermModel::langmuir(properties.langmuir_k);
+        ThermalFluidSimulator {
+            properties,
+            conditions,
+            isotherm,
+            max_allowed_risk: DEFAULT_MAX_ALLOWED_RISK,
+            efficiency_mode: EfficiencyMode::default(),
+            hysteresis_factor: 1.0,
+            working_fluid: WorkingFluid::water(),
+            heat_exchanger: None,
+            heat_transfer_coeff: None,
+            stability_policy: StabilityPolicy::default(),
+        }
+    }
+
+    /// Create a simulator, validating the properties and conditions up front
+    ///
+    /// Fails with `InvalidProperties` or `InvalidConditions` listing every
+    /// violation, instead of at the first simulation. `new` skips the checks.
+    pub fn try_new(
+        properties: MOFThermalProperties,
+        conditions: OperatingConditions,
+    ) -> Result<Self, ThermalError> {
+        properties.validate()?;
+        conditions.validate()?;
+        Ok(Self::new(properties, conditions))
+    }
+
+    /// Create a simulator from a TOML or JSON `SimulationConfig` file
+    ///
+    /// Built through `try_new`, so bad properties or conditions in the file
+    /// fail here rather than at the first simulation.
+    pub fn from_config_file<P: AsRef<Path>>(path: P) -> Result<Self, ThermalError> {
+        let config = SimulationConfig::from_file(path)?;
+        Self::try_new(config.properties, config.conditions)
+    }
+
+    /// Set the risk threshold enforced by `simulate_temperature_swing_safe`
+    ///
+    /// Risk scores lie in [0, 1], so the threshold must too; anything else
+    /// fails with `InvalidParameter` at simulation time.
+    pub fn with_max_allowed_risk(mut self, max_allowed_risk: f64) -> Self {
+        self.max_allowed_risk = max_allowed_risk;
+        self
+    }
+
+    /// Replace the isotherm model used for uptake
+    pub fn with_isotherm(mut self, isotherm: IsothermModel) -> Self {
+        self.isotherm = isotherm;
+        self
+    }
+
+    /// Isotherm model used for uptake
+    pub fn isotherm(&self) -> &IsothermModel {
+        &self.isotherm
+    }
+
+    /// Scale the isotherm's desorption branch by `hysteresis_factor` (≥ 1)
+    ///
+    /// Hysteretic MOFs hold more water on desorption than the adsorption
+    /// branch predicts at the same pressure, so the residual loading at the
+    /// peak temperature is multiplied by this factor (capped at the adsorbed
+    /// loading). 1.0, the default, means no hysteresis.
+    pub fn with_hysteresis_factor(mut self, hysteresis_factor: f64) -> Self {
+        self.hysteresis_factor = hysteresis_factor;
+        self
+    }
+
+    /// Desorption-branch scale applied to the residual loading
+    pub fn hysteresis_factor(&self) -> f64 {
+        self.hysteresis_factor
+    }
+
+    /// Choose how `thermal_efficiency` is normalized (and so what optimizers maximize)
+    pub fn with_efficiency_mode(mut self, efficiency_mode: EfficiencyMode) -> Self {
+        self.efficiency_mode = efficiency_mode;
+        self
+    }
+
+    /// Normalization used for `thermal_efficiency`
+    pub fn efficiency_mode(&self) -> EfficiencyMode {
+        self.efficiency_mode
+    }
+
+    /// Cycle a sorbate other than water
+    pub fn with_working_fluid(mut self, working_fluid: WorkingFluid) -> Self {
+        self.working_fluid = working_fluid;
+        self
+    }
+
+    /// Sorbate whose molar mass and latent heat enter the energy balance
+    pub fn working_fluid(&self) -> WorkingFluid {
+        self.working_fluid
+    }
+
+    /// Recover desorption heat through `heat_exchanger` into the inlet air
+    ///
+    /// The bed's heat capacity leaves at the peak temperature and is
+    /// exchanged against air at ambient; what it recovers is subtracted from
+    /// `energy_consumption_kj` and added to `recovered_energy_kj`.
+    pub fn with_heat_exchanger(mut self, heat_exchanger: HeatExchanger) -> Self {
+        self.heat_exchanger = Some(heat_exchanger);
+        self
+    }
+
+    /// Heat exchanger on the desorption stream, if any
+    pub fn heat_exchanger(&self) -> Option<HeatExchanger> {
+        self.heat_exchanger
+    }
+
+    /// Surface film coefficient h (W/(m²·K)) for the lumped-model check
+    ///
+    /// With it set, every simulation computes the bed's Biot number and sets
+    /// `lumped_model_invalid` (logging a warning) when it exceeds 0.1. Without
+    /// it the check is skipped and the flag stays false.
+    pub fn with_heat_transfer_coeff(mut self, heat_transfer_coeff: f64) -> Self {
+        self.heat_transfer_coeff = Some(heat_transfer_coeff);
+        self
+    }
+
+    /// Surface film coefficient used for the lumped-model check, if any
+    pub fn heat_transfer_coeff(&self) -> Option<f64> {
+        self.heat_transfer_coeff
+    }
+
+    /// Choose how a regeneration setpoint at or above `thermal_stability_k` is handled
+    pub fn with_stability_policy(mut self, stability_policy: StabilityPolicy) -> Self {
+        self.stability_policy = stability_policy;
+        self
+    }
+
+    /// Handling of regeneration at or above the stability limit
+    pub fn stability_policy(&self) -> StabilityPolicy {
+        self.stability_policy
+    }
+
+    /// Copy of this simulator with different operating conditions
+    fn with_conditions(&self, conditions: OperatingConditions) -> Self {
+        ThermalFluidSimulator {
+            conditions,
+            ..self.clone()
+        }
+    }
+
+    /// Run temperature-swing adsorption simulation
+    pub fn simulate_temperature_swing(&self) -> Result<SimulationResults, ThermalError> {
+        self.validate_inputs()?;
+        self.simulate_swing_with(&self.swing_invariants())
+    }
+
+    /// Cache the setpoint-independent terms for fast regeneration sweeps
+    ///
+    /// Validates the simulator once; see `SimulationContext`.
+    pub fn context(&self) -> Result<SimulationContext, ThermalError> {
+        self.validate_inputs()?;
+        Ok(SimulationContext {
+            simulator: self.clone(),
+            invariants: self.swing_invariants(),
+        })
+    }
+
+    /// Terms of the swing model that do not depend on the regeneration setpoint
+    fn swing_invariants(&self) -> SwingInvariants {
+        let ambient_capacity = self.saturation_capacity_g_per_g(self.conditions.ambient_temp_k);
+        SwingInvariants {
+            adsorbed_g_per_g: ambient_capacity * self.estimate_uptake_fraction(),
+            uptake_efficiency: self.uptake_efficiency(),
+            exotherm_peak_k: self.adsorption_peak_temperature_k(),
+            supplied_water_kg: self.supplied_water_kg(),
+        }
+    }
+
+    /// Temperature-swing model on validated inputs with precomputed invariants
+    fn simulate_swing_with(
+        &self,
+        invariants: &SwingInvariants,
+    ) -> Result<SimulationResults, ThermalError> {
+        let degraded = self.conditions.regeneration_temp_k >= self.properties.thermal_stability_k;
+        if degraded && self.stability_policy == StabilityPolicy::Error {
+            return Err(ThermalError::StabilityExceeded {
+                regeneration_temp_k: self.conditions.regeneration_temp_k,
+                thermal_stability_k: self.properties.thermal_stability_k,
+            });
+        }
+
+        // Temperature differential actually achieved during the heating half-cycle
+        let peak_temp = self.peak_temperature_k();
+        let delta_t = peak_temp - self.conditions.ambient_temp_k;
+
+        // Partial desorption cycles only that share of the bed
+        let bed_mass = self.regenerated_mass_kg();
+        // Working capacity: equilibrium loading at ambient minus the residual
+        // loading left at the peak regeneration temperature, both with the
+        // temperature-dependent saturation capacity. g/g is a mass ratio, so
+        // loading × bed mass (kg) gives kg of water per cycle
+        let working_capacity =
+            self.working_capacity_g_per_g(invariants.adsorbed_g_per_g, peak_temp);
+        let uptake_efficiency = invariants.uptake_efficiency;
+        // A destroyed framework releases nothing useful
+        let desorbed = if degraded {
+            0.0
+        } else {
+            // The bed cannot take up more water than the airflow brings in
+            (working_capacity * uptake_efficiency * bed_mass).min(invariants.supplied_water_kg)
+        };
+        // Only the vapor the condenser can liquefy counts as yield
+        let condensed_fraction = self.condensed_fraction(peak_temp);
+        let water_yield = desorbed * condensed_fraction;
+
+        // Energy required for heating (simplified model)
+        // E = m * c_p * ΔT + Q_ads, c_p in J/(kg·K) -> kJ; the kJ/mol heat of
+        // adsorption is charged per mole of water actually desorbed
+        let sensible_energy = bed_mass * self.properties.specific_heat * delta_t / J_PER_KJ;
+        let adsorption_energy = self.desorption_heat_kj(desorbed);
+        let gross_energy = sensible_energy + adsorption_energy;
+
+        // Heat recovered from the previous desorption phase preheats this
+        // cycle; an exchanger on the hot stream recovers more, never beyond
+        // the heat still to be supplied
+        let mut recovered_energy = gross_energy * self.conditions.heat_recovery_fraction;
+        if let Some(exchanger) = self.heat_exchanger {
+            let bed_heat_capacity = bed_mass * self.properties.specific_heat / J_PER_KJ;
+            let exchanged = exchanger.recovered_energy_kj(
+                peak_temp,
+                self.conditions.ambient_temp_k,
+                bed_heat_capacity,
+            );
+            recovered_energy += exchanged.min(gross_energy - recovered_energy);
+        }
+        let heating_energy = gross_energy - recovered_energy;
+
+        let efficiency = self.thermal_efficiency(water_yield, heating_energy, peak_temp);
+
+        // COP: latent heat carried by the captured water / energy consumed
+        let cop = if heating_energy > 0.0 {
+            self.working_fluid.latent_heat_kj_per_kg * water_yield / heating_energy
+        } else {
+            0.0
+        };
+
+        // Headline metric: kWh per liter (1 kg water ≈ 1 L); no water is infinite cost
+        let energy_per_liter = if water_yield > 0.0 {
+            heating_energy / KJ_PER_KWH / water_yield
+        } else {
+            f64::INFINITY
+        };
+
+        // Passive cooling back to ambient before the next adsorption phase
+        let cooling_time = self.cooling_time_s(peak_temp);
+        let insufficient_cycle_time = cooling_time > self.conditions.cycle_time_seconds / 2.0;
+
+        // The lumped heating curve assumes a uniform bed temperature
+        let lumped_model_invalid = match self.heat_transfer_coeff {
+            Some(h) => {
+                let biot = self.biot_number(BED_CHARACTERISTIC_LENGTH_M, h);
+                let invalid = biot > MAX_LUMPED_BIOT_NUMBER;
+                if invalid {
+                    warn!(
+                        "FIPS {}: Biot number {:.3} exceeds {}; lumped model is inaccurate",
+                        self.properties.fips, biot, MAX_LUMPED_BIOT_NUMBER
+                    );
+                }
+                invalid
+            }
+            None => false,
+        };
+
+        // Risk assessment based on thermal stability
+        let risk_score = if degraded {
+            warn!(
+                "FIPS {}: regeneration at {:.1} K reaches the {:.1} K stability limit",
+                self.properties.fips,
+                self.conditions.regeneration_temp_k,
+                self.properties.thermal_stability_k
+            );
+            1.0
+        } else {
+            self.risk_with_exotherm(peak_temp, invariants.exotherm_peak_k)
+        };
+
+        // Valid inputs can still overflow (e.g. an extreme heat of adsorption
+        // in the capacity exponential); never report non-finite results
+        let outputs = [
+            ("water_yield_kg", water_yield),
+            ("energy_consumption_kj", heating_energy),
+            ("max_temperature_k", peak_temp),
+            ("thermal_efficiency", efficiency),
+            ("cooling_time_seconds", cooling_time),
+            ("risk_score", risk_score),
+        ];
+        if let Some((name, value)) = outputs.iter().find(|(_, value)| !value.is_finite()) {
+            return Err(ThermalError::InvalidProperties(format!(
+                "properties make {} non-finite ({})",
+                name, value
+            )));
+        }
+
+        trace!(
+            "FIPS {}: regen {:.1} K -> peak {:.1} K, yield {:.4} kg, \
+             energy {:.2} kJ, efficiency {:.3e}, risk {:.3}",
+            self.properties.fips,
+            self.conditions.regeneration_temp_k,
+            peak_temp,
+            water_yield,
+            heating_energy,
+            efficiency,
+            risk_score
+        );
+
+        let results = SimulationResults {
+            water_yield_kg: water_yield,
+            uptake_efficiency,
+            mass_transfer_limited: uptake_efficiency < EQUILIBRIUM_UPTAKE_EFFICIENCY,
+            energy_consumption_kj: heating_energy,
+            sensible_energy_kj: sensible_energy,
+            adsorption_energy_kj: adsorption_energy,
+            recovered_energy_kj: recovered_energy,
+            max_temperature_k: peak_temp,
+            stability_margin_k: self.properties.thermal_stability_k - peak_temp,
+            cooling_time_seconds: cooling_time,
+            insufficient_cycle_time,
+            lumped_model_invalid,
+            degraded,
+            condensed_fraction,
+            thermal_efficiency: efficiency,
+            efficiency_mode: self.efficiency_mode,
+            coefficient_of_performance: cop,
+            energy_per_liter_kwh: energy_per_liter,
+            risk_score,
+            schema_version: CURRENT_SCHEMA_VERSION,
+        };
+        debug_assert!(
+            self.validate_energy_balance(&results).is_ok(),
+            "{:?}",
+            self.validate_energy_balance(&results)
+        );
+        Ok(results)
+    }
+
+    /// Cross-check the energy terms of a temperature-swing result
+    ///
+    /// `energy_consumption_kj` must equal sensible + adsorption - recovered,
+    /// every component must be non-negative (recovery enters with a minus
+    /// sign, so it too is stored as a positive amount), recovery may not
+    /// exceed the gross heat, and the sensible term must match heating this
+    /// simulator's regenerated bed mass from ambient to `max_temperature_k`.
+    /// Meant for results of this simulator's temperature swing; pressure-swing
+    /// and layered results book energy differently.
+    pub fn validate_energy_balance(&self, results: &SimulationResults) -> Result<(), ThermalError> {
+        let mut violations = Vec::new();
+        let components = [
+            ("sensible_energy_kj", results.sensible_energy_kj),
+            ("adsorption_energy_kj", results.adsorption_energy_kj),
+            ("recovered_energy_kj", results.recovered_energy_kj),
+        ];
+        for (name, value) in components {
+            if !(value >= 0.0 && value.is_finite()) {
+                violations.push(format!("{} must be non-negative (got {})", name, value));
+            }
+        }
+
+        let close = |a: f64, b: f64| (a - b).abs() <= ENERGY_BALANCE_TOLERANCE * a.abs().max(1.0);
+        let gross = results.sensible_energy_kj + results.adsorption_energy_kj;
+        if results.recovered_energy_kj > gross && !close(results.recovered_energy_kj, gross) {
+            violations.push(format!(
+                "recovered {} kJ exceeds the {} kJ gross heat",
+                results.recovered_energy_kj, gross
+            ));
+        }
+        let net = gross - results.recovered_energy_kj;
+        if !close(results.energy_consumption_kj, net) {
+            violations.push(format!(
+                "energy_consumption_kj {} != sensible + adsorption - recovered = {}",
+                results.energy_consumption_kj, net
+            ));
+        }
+        let sensible = self.regenerated_mass_kg()
+            * self.properties.specific_heat
+            * (results.max_temperature_k - self.conditions.ambient_temp_k)
+            / J_PER_KJ;
+        if !close(results.sensible_energy_kj, sensible) {
+            violations.push(format!(
+                "sensible_energy_kj {} != bed heat capacity × temperature rise = {}",
+                results.sensible_energy_kj, sensible
+            ));
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ThermalError::EnergyImbalance(violations.join("; ")))
+        }
+    }
+
+    /// Run the swing simulation, rejecting results above the allowed risk
+    ///
+    /// Use `simulate_temperature_swing` to analyse unsafe points without the check.
+    pub fn simulate_temperature_swing_safe(&self) -> Result<SimulationResults, ThermalError> {
+        let results = self.simulate_temperature_swing()?;
+        if results.risk_score > self.max_allowed_risk {
+            return Err(ThermalError::UnsafeOperation {
+                risk_score: results.risk_score,
+                max_allowed: self.max_allowed_risk,
+            });
+        }
+        Ok(results)
+    }
+
+    /// Run pressure-swing adsorption simulation
+    ///
+    /// Adsorbs from feed air at `pressure_atm` and desorbs by dropping the
+    /// bed to `low_pressure_atm` at ambient temperature. The working capacity
+    /// is the isotherm loading difference between the two pressures, and the
+    /// energy is the isothermal work to recompress the released vapor,
+    /// n·R·T·ln(P_high/P_low). The bed never leaves ambient, so risk is low.
+    /// Uptake is capped by `airflow_m3_per_s` and yield scaled by the
+    /// condenser's `condensed_fraction` as in the temperature swing, with the
+    /// pumped vapor at ambient rather than a peak temperature.
+    pub fn simulate_pressure_swing(
+        &self,
+        low_pressure_atm: f64,
+    ) -> Result<SimulationResults, ThermalError> {
+        self.validate_inputs()?;
+        let high_pressure_atm = self.conditions.pressure_atm;
+        if !(low_pressure_atm > 0.0 && low_pressure_atm < high_pressure_atm) {
+            return Err(ThermalError::InvalidParameter(format!(
+                "low_pressure_atm must be in (0, {}) (got {})",
+                high_pressure_atm, low_pressure_atm
+            )));
+        }
+
+        let ambient = self.conditions.ambient_temp_k;
+        // Partial desorption evacuates only that share of the bed
+        let bed_mass = self.regenerated_mass_kg();
+        let mut evacuated = self.clone();
+        evacuated.conditions.pressure_atm = low_pressure_atm;
+
+        // Isothermal working capacity; desorption follows the hysteretic branch
+        let adsorbed = self.equilibrium_loading_g_per_g(ambient);
+        let residual = (self.hysteresis_factor * evacuated.equilibrium_loading_g_per_g(ambient))
+            .min(adsorbed);
+        let working_capacity = (adsorbed - residual).max(0.0);
+        let uptake_efficiency = self.uptake_efficiency();
+        // The bed cannot take up more water than the airflow brings in
+        let desorbed =
+            (working_capacity * uptake_efficiency * bed_mass).min(self.supplied_water_kg());
+        // The pumped stream leaves the bed at ambient; only what the
+        // condenser liquefies counts as yield
+        let condensed_fraction = self.condensed_fraction(ambient);
+        let water_yield = desorbed * condensed_fraction;
+
+        // Vacuum pump work: moles of water vapor lifted from P_low to P_high
+        let moles = desorbed * G_PER_KG / self.working_fluid.molar_mass_g_per_mol;
+        let compression_energy = moles * R_J_PER_MOL_K * ambient
+            * (high_pressure_atm / low_pressure_atm).ln()
+            / J_PER_KJ;
+
+        let efficiency = self.thermal_efficiency(water_yield, compression_energy, ambient);
+        let cop = if compression_energy > 0.0 {
+            self.working_fluid.latent_heat_kj_per_kg * water_yield / compression_energy
+        } else {
+            0.0
+        };
+        let energy_per_liter = if water_yield > 0.0 {
+            compression_energy / KJ_PER_KWH / water_yield
+        } else {
+            f64::INFINITY
+        };
+        let risk_score = self.calculate_risk_score(ambient);
+
+        trace!(
+            "FIPS {}: PSA {:.3} -> {:.3} atm, yield {:.4} kg, energy {:.2} kJ, risk {:.3}",
+            self.properties.fips,
+            high_pressure_atm,
+            low_pressure_atm,
+            water_yield,
+            compression_energy,
+            risk_score
+        );
+
+        Ok(SimulationResults {
+            water_yield_kg: water_yield,
+            uptake_efficiency,
+            mass_transfer_limited: uptake_efficiency < EQUILIBRIUM_UPTAKE_EFFICIENCY,
+            energy_consumption_kj: compression_energy,
+            sensible_energy_kj: 0.0,
+            adsorption_energy_kj: 0.0,
+            recovered_energy_kj: 0.0,
+            max_temperature_k: ambient,
+            stability_margin_k: self.properties.thermal_stability_k - ambient,
+            cooling_time_seconds: 0.0,
+            insufficient_cycle_time: false,
+            lumped_model_invalid: false,
+            degraded: false,
+            condensed_fraction,
+            thermal_efficiency: efficiency,
+            efficiency_mode: self.efficiency_mode,
+            coefficient_of_performance: cop,
+            energy_per_liter_kwh: energy_per_liter,
+            risk_score,
+            schema_version: CURRENT_SCHEMA_VERSION,
+        })
+    }
+
+    /// Validate conditions, properties, isotherm, hysteresis and risk
+    /// threshold before simulating
+    fn validate_inputs(&self) -> Result<(), ThermalError> {
+        self.conditions.validate()?;
+        self.properties.validate()?;
+        self.isotherm.validate()?;
+        self.working_fluid.validate()?;
+        if let Some(h) = self.heat_transfer_coeff {
+            if !(is_positive(h) && h.is_finite()) {
+                return Err(ThermalError::InvalidParameter(format!(
+                    "heat_transfer_coeff must be positive and finite (got {})",
+                    h
+                )));
+            }
+        }
+        if let Some(exchanger) = &self.heat_exchanger {
+            exchanger.validate()?;
+        }
+        if !(self.hysteresis_factor >= 1.0 && self.hysteresis_factor.is_finite()) {
+            return Err(ThermalError::InvalidParameter(format!(
+                "hysteresis_factor must be at least 1 (got {})",
+                self.hysteresis_factor
+            )));
+        }
+        // NaN fails the range check, so it cannot switch the risk guard off
+        if !(0.0..=1.0).contains(&self.max_allowed_risk) {
+            return Err(ThermalError::InvalidParameter(format!(
+                "max_allowed_risk must be in [0, 1] (got {})",
+                self.max_allowed_risk
+            )));
+        }
+        Ok(())
+    }
+
+    /// Simulate across evenly spaced relative humidities, endpoints included
+    ///
+    /// Every other condition is held fixed; the simulator is not modified.
+    /// `progress`, if given, is called after each humidity is simulated.
+    pub fn sweep_humidity(
+        &self,
+        min: f64,
+        max: f64,
+        steps: usize,
+        progress: Option<Progress<'_>>,
+    ) -> Result<Vec<(f64, SimulationResults)>, ThermalError> {
+        if !(0.0 <= min && min < max && max <= 1.0) {
+            return Err(ThermalError::InvalidParameter(format!(
+                "humidity range must satisfy 0 <= min < max <= 1 (got {} to {})",
+                min, max
+            )));
+        }
+        if steps < 2 {
+            return Err(ThermalError::InvalidParameter(format!(
+                "humidity sweep needs at least 2 steps (got {})",
+                steps
+            )));
+        }
+
+        let spacing = (max - min) / (steps - 1) as f64;
+        (0..steps)
+            .map(|i| {
+                let humidity = min + i as f64 * spacing;
+                let conditions = OperatingConditions {
+                    humidity,
+                    ..self.conditions.clone()
+                };
+                let results = self.with_conditions(conditions).simulate_temperature_swing()?;
+                if let Some(progress) = progress {
+                    progress(i + 1, steps);
+                }
+                Ok((humidity, results))
+            })
+            .collect()
+    }
+
+    /// Run one swing cycle per weather sample and accumulate the totals
+    ///
+    /// Each sample replaces the ambient temperature, humidity and pressure of
+    /// the configured conditions; everything else, including the regeneration
+    /// setpoint and cycle time, is held. With the default one-hour cycle an
+    /// hourly series gives one cycle per hour. Samples whose simulation fails
+    /// (e.g. humidity out of range) are counted in `failed_samples` and left
+    /// out of the totals.
+    pub fn simulate_timeseries(&self, weather: &[WeatherSample]) -> AnnualSummary {
+        let mut summary = AnnualSummary {
+            samples: 0,
+            failed_samples: 0,
+            water_yield_kg: 0.0,
+            energy_consumption_kj: 0.0,
+            max_risk_score: 0.0,
+        };
+        for sample in weather {
+            let conditions = OperatingConditions {
+                ambient_temp_k: sample.temp_k,
+                humidity: sample.humidity,
+                pressure_atm: sample.pressure_atm,
+                ..self.conditions.clone()
+            };
+            match self.with_conditions(conditions).simulate_temperature_swing() {
+                Ok(results) => {
+                    summary.samples += 1;
+                    summary.water_yield_kg += results.water_yield_kg;
+                    summary.energy_consumption_kj += results.energy_consumption_kj;
+                    summary.max_risk_score = summary.max_risk_score.max(results.risk_score);
+                }
+                Err(_) => summary.failed_samples += 1,
+            }
+        }
+        summary
+    }
+
+    /// Simulate one cycle whose feed humidity varies over the adsorption phase
+    ///
+    /// `profile` holds relative humidities (0-1) at equal time steps across
+    /// the adsorption half-cycle, so each entry carries weight 1/len. Loading
+    /// at ambient is the time-weighted mean over the profile; the adsorption
+    /// exotherm is taken at the most humid step, since risk tracks the peak.
+    /// The configured `humidity` is ignored. A one-entry or constant profile
+    /// matches `simulate_temperature_swing` at that humidity.
+    pub fn simulate_with_humidity_profile(
+        &self,
+        profile: &[f64],
+    ) -> Result<SimulationResults, ThermalError> {
+        if profile.is_empty() {
+            return Err(ThermalError::InvalidParameter(
+                "humidity profile needs at least one step".to_string(),
+            ));
+        }
+
+        let weight = 1.0 / profile.len() as f64;
+        let mut invariants = SwingInvariants {
+            adsorbed_g_per_g: 0.0,
+            uptake_efficiency: self.uptake_efficiency(),
+            exotherm_peak_k: f64::NEG_INFINITY,
+            supplied_water_kg: 0.0,
+        };
+        for &humidity in profile {
+            let step = self.with_conditions(OperatingConditions {
+                humidity,
+                ..self.conditions.clone()
+            });
+            step.validate_inputs()?;
+            let step_invariants = step.swing_invariants();
+            invariants.adsorbed_g_per_g += weight * step_invariants.adsorbed_g_per_g;
+            invariants.supplied_water_kg += weight * step_invariants.supplied_water_kg;
+            invariants.exotherm_peak_k =
+                invariants.exotherm_peak_k.max(step_invariants.exotherm_peak_k);
+        }
+        self.simulate_swing_with(&invariants)
+    }
+
+    /// Simulate every regeneration temperature × humidity pair, for heatmaps
+    ///
+    /// Every other condition is held fixed; the simulator is not modified.
+    /// Combinations that fail to simulate are stored as `None` so the grid
+    /// stays dense.
+    pub fn sweep_grid(&self, temp_axis: &[f64], humidity_axis: &[f64]) -> SweepGrid {
+        let cells = temp_axis
+            .iter()
+            .flat_map(|&regeneration_temp_k| {
+                humidity_axis.iter().map(move |&humidity| {
+                    let conditions = OperatingConditions {
+                        regeneration_temp_k,
+                        humidity,
+                        ..self.conditions.clone()
+                    };
+                    self.with_conditions(conditions).simulate_temperature_swing().ok()
+                })
+            })
+            .collect();
+        SweepGrid {
+            temp_axis: temp_axis.to_vec(),
+            humidity_axis: humidity_axis.to_vec(),
+            cells,
+        }
+    }
+
+    /// Simulate across evenly spaced ambient temperatures, endpoints included
+    ///
+    /// The regeneration setpoint is held fixed and the simulator is not
+    /// modified. Points at or above the setpoint have no temperature swing
+    /// and are skipped rather than reported.
+    pub fn sweep_ambient_temp(
+        &self,
+        min_k: f64,
+        max_k: f64,
+        steps: usize,
+    ) -> Result<Vec<(f64, SimulationResults)>, ThermalError> {
+        if !(is_positive(min_k) && min_k < max_k && max_k.is_finite()) {
+            return Err(ThermalError::InvalidParameter(format!(
+                "ambient range must satisfy 0 < min < max (got {} to {})",
+                min_k, max_k
+            )));
+        }
+        if steps < 2 {
+            return Err(ThermalError::InvalidParameter(format!(
+                "ambient sweep needs at least 2 steps (got {})",
+                steps
+            )));
+        }
+
+        let spacing = (max_k - min_k) / (steps - 1) as f64;
+        (0..steps)
+            .map(|i| min_k + i as f64 * spacing)
+            .filter(|&ambient| ambient < self.conditions.regeneration_temp_k)
+            .map(|ambient_temp_k| {
+                let conditions = OperatingConditions {
+                    ambient_temp_k,
+                    ..self.conditions.clone()
+                };
+                let results = self.with_conditions(conditions).simulate_temperature_swing()?;
+                Ok((ambient_temp_k, results))
+            })
+            .collect()
+    }
+
+    /// Lumped-capacitance thermal time constant of the bed (s)
+    ///
+    /// τ = ρ·c_p·L² / k, with L the bed's characteristic conduction length, so
+    /// conductive, low-heat-capacity MOFs respond faster.
+    pub fn thermal_time_constant_s(&self) -> f64 {
+        self.properties.density
+            * self.properties.specific_heat
+            * BED_CHARACTERISTIC_LENGTH_M.powi(2)
+            / self.properties.thermal_conductivity
+    }
+
+    /// Biot number Bi = h·L / k of a bed with conduction length `characteristic_length_m`
+    ///
+    /// `heat_transfer_coeff` is the surface film coefficient (W/(m²·K)). The
+    /// lumped-capacitance model holds for Bi ≲ 0.1; beyond that the bed's
+    /// interior lags its surface.
+    pub fn biot_number(&self, characteristic_length_m: f64, heat_transfer_coeff: f64) -> f64 {
+        heat_transfer_coeff * characteristic_length_m / self.properties.thermal_conductivity
+    }
+
+    /// Time for the bed to cool from `peak_temp_k` to near ambient (s)
+    ///
+    /// Exponential decay with the lumped time constant: the excess over
+    /// ambient falls to `COOLING_TOLERANCE_K` after τ·ln(ΔT_peak / tolerance).
+    fn cooling_time_s(&self, peak_temp_k: f64) -> f64 {
+        let excess = peak_temp_k - self.conditions.ambient_temp_k;
+        if excess > COOLING_TOLERANCE_K {
+            self.thermal_time_constant_s() * (excess / COOLING_TOLERANCE_K).ln()
+        } else {
+            0.0
+        }
+    }
+
+    /// Adiabatic bed temperature rise from the adsorption exotherm (K)
+    ///
+    /// Heat of adsorption released by one half-cycle's captured loading,
+    /// q = q_eq(T_amb) × uptake efficiency, over the heat capacity of the
+    /// loaded bed: ΔT = q·ΔH/M / (c_p + q·c_p,ads). This is the rise with no
+    /// heat loss at all, i.e. for instantaneous uptake.
+    pub fn adsorption_temperature_rise(&self) -> f64 {
+        let captured = self.equilibrium_loading_g_per_g(self.conditions.ambient_temp_k)
+            * self.uptake_efficiency();
+        let heat_capacity =
+            self.properties.specific_heat + captured * ADSORBED_PHASE_SPECIFIC_HEAT;
+        self.desorption_heat_kj(captured) * J_PER_KJ / heat_capacity
+    }
+
+    /// Peak bed temperature from the adsorption exotherm (K)
+    ///
+    /// Heat is released at the linear-driving-force rate k_m while the bed
+    /// sheds it with time constant τ, which peaks at a^(1/(1−a)) of the
+    /// adiabatic rise for a = k_m·τ (1/e at a = 1). Uptake much faster than
+    /// the bed cools approaches the adiabatic rise.
+    fn adsorption_peak_temperature_k(&self) -> f64 {
+        let a = self.properties.mass_transfer_coeff * self.thermal_time_constant_s();
+        let peak_fraction = if (a - 1.0).abs() < 1e-9 {
+            (-1.0f64).exp()
+        } else {
+            a.powf(1.0 / (1.0 - a))
+        };
+        self.conditions.ambient_temp_k + self.adsorption_temperature_rise() * peak_fraction
+    }
+
+    /// Peak bed temperature reached during the heating half-cycle (K)
+    ///
+    /// The bed approaches the setpoint exponentially, so a cycle shorter than a
+    /// few time constants never reaches `regeneration_temp_k`.
+    fn peak_temperature_k(&self) -> f64 {
+        self.temperature_at(self.conditions.cycle_time_seconds / 2.0)
+    }
+
+    /// Water produced per day by back-to-back cycles (kg, ≈ liters)
+    ///
+    /// A cycle occupies `cycle_time_seconds`, or the heating half-cycle plus
+    /// the full cooling time when cooling overruns the second half. Only
+    /// whole cycles count, so a period longer than a day produces zero.
+    pub fn daily_water_production_kg(&self) -> Result<f64, ThermalError> {
+        let results = self.simulate_temperature_swing()?;
+        Ok(self.daily_production_from(&results))
+    }
+
+    /// Daily production from one cycle's results at these conditions (kg)
+    fn daily_production_from(&self, results: &SimulationResults) -> f64 {
+        let heating_time = self.conditions.cycle_time_seconds / 2.0;
+        let period = self
+            .conditions
+            .cycle_time_seconds
+            .max(heating_time + results.cooling_time_seconds);
+        let cycles = (SECONDS_PER_DAY / period).floor();
+        cycles * results.water_yield_kg
+    }
+
+    /// Bed mass needed to produce `target_daily_kg` of water per day
+    ///
+    /// Below the airflow cap, yield scales linearly with bed mass and the
+    /// cycle period does not depend on it, so this inverts
+    /// `daily_water_production_kg` for a 1 kg bed with unlimited air. Once the
+    /// supplied water binds, extra mass adds nothing. Fails if the conditions
+    /// produce no water in a day, or if `airflow_m3_per_s` cannot carry in
+    /// enough water to meet the target at any bed mass.
+    pub fn required_bed_mass_kg(&self, target_daily_kg: f64) -> Result<f64, ThermalError> {
+        if !is_positive(target_daily_kg) || !target_daily_kg.is_finite() {
+            return Err(ThermalError::InvalidParameter(format!(
+                "target daily yield must be positive (got {})",
+                target_daily_kg
+            )));
+        }
+
+        let unit_bed = self.with_conditions(OperatingConditions {
+            bed_mass_kg: 1.0,
+            airflow_m3_per_s: None,
+            ..self.conditions.clone()
+        });
+        let daily_per_kg = unit_bed.daily_water_production_kg()?;
+        if !is_positive(daily_per_kg) {
+            return Err(ThermalError::InvalidConditions(
+                "conditions produce no working capacity, so no bed mass reaches the target"
+                    .to_string(),
+            ));
+        }
+        let bed_mass_kg = target_daily_kg / daily_per_kg;
+
+        // Where the airflow caps uptake, the forward yield is the most any bed
+        // mass can produce
+        if self.conditions.airflow_m3_per_s.is_some() {
+            let sized = self.with_conditions(OperatingConditions {
+                bed_mass_kg,
+                ..self.conditions.clone()
+            });
+            let daily_kg = sized.daily_water_production_kg()?;
+            if daily_kg < target_daily_kg * (1.0 - SIZING_TOLERANCE) {
+                return Err(ThermalError::InvalidConditions(format!(
+                    "airflow supplies at most {} kg/day, below the {} kg/day target",
+                    daily_kg, target_daily_kg
+                )));
+            }
+        }
+        Ok(bed_mass_kg)
+    }
+
+    /// Bed temperature trace over one cycle as (time_seconds, temperature_k)
+    ///
+    /// `samples` evenly spaced points from 0 to `cycle_time_seconds`. The bed
+    /// heats from ambient toward the setpoint for the first half-cycle, then
+    /// cools back toward ambient, both with the lumped time constant.
+    pub fn temperature_profile(&self, samples: usize) -> Vec<(f64, f64)> {
+        let cycle_time = self.conditions.cycle_time_seconds;
+        let spacing = if samples > 1 {
+            cycle_time / (samples - 1) as f64
+        } else {
+            0.0
+        };
+
+        (0..samples)
+            .map(|i| {
+                let time = i as f64 * spacing;
+                (time, self.temperature_at(time))
+            })
+            .collect()
+    }
+
+    /// Bed temperature (K) at a time within the cycle
+    fn temperature_at(&self, time_s: f64) -> f64 {
+        let tau = self.thermal_time_constant_s();
+        let heating_time = self.conditions.cycle_time_seconds / 2.0;
+        let ambient = self.conditions.ambient_temp_k;
+
+        if time_s <= heating_time {
+            let delta_t = self.conditions.regeneration_temp_k - ambient;
+            ambient + delta_t * (1.0 - (-time_s / tau).exp())
+        } else {
+            let peak_rise = self.peak_temperature_k() - ambient;
+            ambient + peak_rise * (-(time_s - heating_time) / tau).exp()
+        }
+    }
+
+    /// Run `n` consecutive cycles with capacity degrading each cycle
+    ///
+    /// Cycle `i` (zero-based) sees `max_uptake_g_per_g` scaled by
+    /// `(1 - capacity_decay_per_cycle)^i`, so yields are non-increasing.
+    pub fn simulate_cycles(&self, n: usize) -> Result<Vec<SimulationResults>, ThermalError> {
+        self.conditions.validate()?;
+
+        let retention = 1.0 - self.conditions.capacity_decay_per_cycle;
+        let mut capacity = self.properties.max_uptake_g_per_g;
+        let mut cycles = Vec::with_capacity(n);
+
+        for _ in 0..n {
+            let mut degraded = self.clone();
+            degraded.properties.max_uptake_g_per_g = capacity;
+            cycles.push(degraded.simulate_temperature_swing()?);
+            capacity *= retention;
+        }
+
+        Ok(cycles)
+    }
+
+    /// Run `n` cycles as `simulate_cycles` does and detect steady state
+    ///
+    /// Steady state starts at the first cycle whose yield changes by less
+    /// than `tolerance_kg` from the cycle before (see `steady_state_cycle`);
+    /// the steady-state yield is the mean over that cycle and the rest.
+    pub fn simulate_cycles_summary(
+        &self,
+        n: usize,
+        tolerance_kg: f64,
+    ) -> Result<CycleSummary, ThermalError> {
+        if !(is_positive(tolerance_kg) && tolerance_kg.is_finite()) {
+            return Err(ThermalError::InvalidParameter(format!(
+                "steady-state tolerance must be positive and finite (got {})",
+                tolerance_kg
+            )));
+        }
+
+        let cycles = self.simulate_cycles(n)?;
+        let yields: Vec<f64> = cycles.iter().map(|r| r.water_yield_kg).collect();
+        let steady_state_cycle = steady_state_cycle(&yields, tolerance_kg);
+        let steady_state_yield_kg = steady_state_cycle.map(|start| {
+            let settled = &yields[start..];
+            settled.iter().sum::<f64>() / settled.len() as f64
+        });
+        Ok(CycleSummary {
+            cycles,
+            steady_state_cycle,
+            steady_state_yield_kg,
+        })
+    }
+
+    /// Simulate a layered bed under this simulator's conditions
+    ///
+    /// Each layer runs on its share of `bed_mass_kg` with its own properties
+    /// and Langmuir isotherm, so yields and energies add up across layers.
+    /// The least stable layer governs safety: `risk_score` and peak
+    /// temperature are the layer maxima, the stability margin the minimum.
+    pub fn simulate_layered(&self, bed: &LayeredBed) -> Result<SimulationResults, ThermalError> {
+        bed.validate()?;
+
+        let mut layer_results = Vec::with_capacity(bed.layers.len());
+        for (properties, &fraction) in bed.layers.iter().zip(&bed.mass_fractions) {
+            // Each layer sees its mass share of the air as well as of the bed
+            let conditions = OperatingConditions {
+                bed_mass_kg: self.conditions.bed_mass_kg * fraction,
+                airflow_m3_per_s: self.conditions.airflow_m3_per_s.map(|a| a * fraction),
+                ..self.conditions.clone()
+            };
+            let layer = ThermalFluidSimulator {
+                max_allowed_risk: self.max_allowed_risk,
+                efficiency_mode: self.efficiency_mode,
+                hysteresis_factor: self.hysteresis_factor,
+                working_fluid: self.working_fluid,
+                heat_exchanger: self.heat_exchanger,
+                heat_transfer_coeff: self.heat_transfer_coeff,
+                stability_policy: self.stability_policy,
+                ..ThermalFluidSimulator::new(properties.clone(), conditions)
+            };
+            layer_results.push(layer.simulate_temperature_swing()?);
+        }
+
+        let sum = |field: fn(&SimulationResults) -> f64| layer_results.iter().map(field).sum();
+        let max = |field: fn(&SimulationResults) -> f64| {
+            layer_results.iter().map(field).fold(f64::NEG_INFINITY, f64::max)
+        };
+        let water_yield: f64 = sum(|r| r.water_yield_kg);
+        let energy: f64 = sum(|r| r.energy_consumption_kj);
+        // Every mode is (useful output) / energy, so the bed's efficiency is
+        // the energy-weighted mean of the layers'
+        let (efficiency, cop) = if energy > 0.0 {
+            let useful: f64 = sum(|r| r.thermal_efficiency * r.energy_consumption_kj);
+            let latent_heat = self.working_fluid.latent_heat_kj_per_kg;
+            (useful / energy, latent_heat * water_yield / energy)
+        } else {
+            (0.0, 0.0)
+        };
+        let energy_per_liter = if water_yield > 0.0 {
+            energy / KJ_PER_KWH / water_yield
+        } else {
+            f64::INFINITY
+        };
+
+        let uptake_efficiency = layer_results
+            .iter()
+            .zip(&bed.mass_fractions)
+            .map(|(r, &fraction)| r.uptake_efficiency * fraction)
+            .sum();
+
+        Ok(SimulationResults {
+            water_yield_kg: water_yield,
+            uptake_efficiency,
+            mass_transfer_limited: layer_results.iter().any(|r| r.mass_transfer_limited),
+            energy_consumption_kj: energy,
+            sensible_energy_kj: sum(|r| r.sensible_energy_kj),
+            adsorption_energy_kj: sum(|r| r.adsorption_energy_kj),
+            recovered_energy_kj: sum(|r| r.recovered_energy_kj),
+            max_temperature_k: max(|r| r.max_temperature_k),
+            stability_margin_k: -max(|r| -r.stability_margin_k),
+            cooling_time_seconds: max(|r| r.cooling_time_seconds),
+            insufficient_cycle_time: layer_results.iter().any(|r| r.insufficient_cycle_time),
+            lumped_model_invalid: layer_results.iter().any(|r| r.lumped_model_invalid),
+            degraded: layer_results.iter().any(|r| r.degraded),
+            condensed_fraction: -max(|r| -r.condensed_fraction),
+            thermal_efficiency: efficiency,
+            efficiency_mode: self.efficiency_mode,
+            coefficient_of_performance: cop,
+            energy_per_liter_kwh: energy_per_liter,
+            risk_score: max(|r| r.risk_score),
+            schema_version: CURRENT_SCHEMA_VERSION,
+        })
+    }
+
+    /// Bed mass regenerated each cycle: `bed_mass_kg` × `desorption_fraction` (kg)
+    ///
+    /// Partial desorption is modeled as cycling only that share of the bed,
+    /// so yield and heating energy both scale with it, in temperature and
+    /// pressure swing alike.
+    fn regenerated_mass_kg(&self) -> f64 {
+        self.conditions.bed_mass_kg * self.conditions.desorption_fraction
+    }
+
+    /// Loading released by heating a bed holding `adsorbed_g_per_g` to `temp_k`
+    fn working_capacity_g_per_g(&self, adsorbed_g_per_g: f64, temp_k: f64) -> f64 {
+        // Desorption follows the hysteretic branch, which retains more water
+        let residual = (self.hysteresis_factor * self.equilibrium_loading_g_per_g(temp_k))
+            .min(adsorbed_g_per_g);
+        (adsorbed_g_per_g - residual).max(0.0)
+    }
+
+    /// Which cap holds back this configuration's yield
+    ///
+    /// Airflow binds when the supplied vapor is below what the bed would
+    /// otherwise take up. Otherwise the smaller of two shortfalls wins if it
+    /// is below 99%: the uptake efficiency (mass transfer over the adsorption
+    /// half-cycle) and the working capacity reached at the peak temperature
+    /// relative to the setpoint (heating cut short by the cycle time). With
+    /// neither, the equilibrium capacity is the limit.
+    ///
+    /// Validates the inputs first. A setpoint at or above
+    /// `thermal_stability_k` fails with `StabilityExceeded`, since a
+    /// destroyed framework has no yield to limit.
+    pub fn limiting_factor(&self) -> Result<LimitingFactor, ThermalError> {
+        self.validate_inputs()?;
+        if self.conditions.regeneration_temp_k >= self.properties.thermal_stability_k {
+            return Err(ThermalError::StabilityExceeded {
+                regeneration_temp_k: self.conditions.regeneration_temp_k,
+                thermal_stability_k: self.properties.thermal_stability_k,
+            });
+        }
+
+        let invariants = self.swing_invariants();
+        let peak_temp = self.peak_temperature_k();
+        let reached = self.working_capacity_g_per_g(invariants.adsorbed_g_per_g, peak_temp);
+        let uptake_kg = reached * invariants.uptake_efficiency * self.regenerated_mass_kg();
+        if invariants.supplied_water_kg < uptake_kg {
+            return Ok(LimitingFactor::Airflow);
+        }
+
+        let at_setpoint = self.working_capacity_g_per_g(
+            invariants.adsorbed_g_per_g,
+            self.conditions.regeneration_temp_k,
+        );
+        let heating_fraction = if at_setpoint > 0.0 { reached / at_setpoint } else { 1.0 };
+        let shortfall = heating_fraction.min(invariants.uptake_efficiency);
+        Ok(if shortfall >= EQUILIBRIUM_UPTAKE_EFFICIENCY {
+            LimitingFactor::EquilibriumCapacity
+        } else if heating_fraction < invariants.uptake_efficiency {
+            LimitingFactor::CycleTime
+        } else {
+            LimitingFactor::MassTransfer
+        })
+    }
+
+    /// Fraction of the equilibrium working capacity loaded per cycle
+    ///
+    /// Linear-driving-force kinetics: loading closes the gap to equilibrium
+    /// at rate `mass_transfer_coeff` over the adsorption half-cycle, reaching
+    /// 1 - e^(-k·t_ads). Long cycles approach 1; fast cycles are limited.
+    pub fn uptake_efficiency(&self) -> f64 {
+        let adsorption_time = self.conditions.cycle_time_seconds / 2.0;
+        (1.0 - (-self.properties.mass_transfer_coeff * adsorption_time).exp()).max(0.0)
+    }
+
+    /// Saturation capacity at a bed temperature (g water / g MOF)
+    ///
+    /// Clausius–Clapeyron-style decline from `max_uptake_g_per_g` at
+    /// 298.15 K: q_sat(T) = q_max · exp(ΔH/R · (1/T − 1/T_ref)), with ΔH the
+    /// heat of adsorption. Hotter beds hold less, which drives desorption.
+    pub fn saturation_capacity_g_per_g(&self, temp_k: f64) -> f64 {
+        let exponent = self.properties.heat_of_adsorption * J_PER_KJ / R_J_PER_MOL_K
+            * (1.0 / temp_k - 1.0 / CAPACITY_REFERENCE_TEMP_K);
+        self.properties.max_uptake_g_per_g * exponent.exp()
+    }
+
+    /// Equilibrium water loading at a bed temperature (g water / g MOF)
+    ///
+    /// Saturation capacity at `temp_k` times the isotherm's fractional uptake
+    /// at the feed vapor's relative pressure there.
+    pub fn equilibrium_loading_g_per_g(&self, temp_k: f64) -> f64 {
+        self.saturation_capacity_g_per_g(temp_k) * self.uptake_fraction_at(temp_k)
+    }
+
+    /// Share of the adsorbed loading released at bed temperature `temp_k`
+    ///
+    /// (q_ads − q_residual(T)) / q_ads at equilibrium, with the residual on
+    /// the hysteretic desorption branch. Zero at ambient, rising with `temp_k`.
+    fn equilibrium_desorption_fraction(&self, temp_k: f64) -> f64 {
+        let adsorbed = self.equilibrium_loading_g_per_g(self.conditions.ambient_temp_k);
+        if !is_positive(adsorbed) {
+            return 0.0;
+        }
+        let residual =
+            (self.hysteresis_factor * self.equilibrium_loading_g_per_g(temp_k)).min(adsorbed);
+        (adsorbed - residual) / adsorbed
+    }
+
+    /// Bed temperature that desorbs `fraction` of the adsorbed loading (K)
+    ///
+    /// Inverts the equilibrium capacity by bisection between ambient and the
+    /// optimizer's safe limit, `thermal_stability_k` × the default safety
+    /// margin. Heating to this setpoint reaches it only when the cycle is
+    /// long compared with the bed's time constant. Fails with
+    /// `InvalidParameter` if `fraction` is outside (0, 1) or needs a hotter bed
+    /// than the safe limit.
+    pub fn temperature_for_desorption_fraction(&self, fraction: f64) -> Result<f64, ThermalError> {
+        self.validate_inputs()?;
+        if !(fraction > 0.0 && fraction < 1.0) {
+            return Err(ThermalError::InvalidParameter(format!(
+                "desorption fraction must be in (0, 1) (got {})",
+                fraction
+            )));
+        }
+
+        let safe_limit_k =
+            self.properties.thermal_stability_k * OptimizationConfig::default().safety_margin;
+        let mut lower = self.conditions.ambient_temp_k;
+        let mut upper = safe_limit_k;
+        if !(upper > lower && self.equilibrium_desorption_fraction(upper) >= fraction) {
+            return Err(ThermalError::InvalidParameter(format!(
+                "desorbing {} of the loading needs more than the safe limit of {:.1} K",
+                fraction, safe_limit_k
+            )));
+        }
+
+        while upper - lower > DESORPTION_TOLERANCE_K {
+            let mid = (lower + upper) / 2.0;
+            if self.equilibrium_desorption_fraction(mid) < fraction {
+                lower = mid;
+            } else {
+                upper = mid;
+            }
+        }
+        Ok(upper)
+    }
+
+    /// Lowest regeneration setpoint that yields any water (K)
+    ///
+    /// Below the bed temperature T* where the hysteretic residual loading
+    /// q_des(T*) first drops under the ambient loading, the working capacity
+    /// is zero. T* is found by bisection up to `thermal_stability_k`, then
+    /// mapped to the setpoint whose lumped heating curve peaks at T* within
+    /// the heating half-cycle. Without hysteresis any swing desorbs, so this
+    /// is `ambient_temp_k` to within the bisection tolerance; if no bed
+    /// temperature below the stability limit desorbs water (e.g. dry feed
+    /// air), it is infinite. Useful as the lower end of an
+    /// `optimize_conditions` window.
+    pub fn min_viable_regeneration_temp(&self) -> f64 {
+        let ambient = self.conditions.ambient_temp_k;
+        let mut lower = ambient;
+        let mut upper = self.properties.thermal_stability_k;
+        if !(upper > lower && self.equilibrium_desorption_fraction(upper) > 0.0) {
+            return f64::INFINITY;
+        }
+        while upper - lower > DESORPTION_TOLERANCE_K {
+            let mid = (lower + upper) / 2.0;
+            if self.equilibrium_desorption_fraction(mid) > 0.0 {
+                upper = mid;
+            } else {
+                lower = mid;
+            }
+        }
+
+        let heating_time = self.conditions.cycle_time_seconds / 2.0;
+        let approach = 1.0 - (-heating_time / self.thermal_time_constant_s()).exp();
+        ambient + (upper - ambient) / approach
+    }
+
+    /// Equilibrium isotherm at ambient temperature, for lab comparison
+    ///
+    /// Returns `(relative_pressure, uptake_g_per_g)` for each of
+    /// `pressure_points` (p/p_sat), using the configured isotherm and the
+    /// saturation capacity, both evaluated at `ambient_temp_k`.
+    pub fn isotherm_curve(&self, pressure_points: &[f64]) -> Vec<(f64, f64)> {
+        let ambient = self.conditions.ambient_temp_k;
+        let heat = self.properties.heat_of_adsorption;
+        let capacity = self.saturation_capacity_g_per_g(ambient);
+        pressure_points
+            .iter()
+            .map(|&p| (p, capacity * self.isotherm.uptake_fraction_at(p, ambient, heat)))
+            .collect()
+    }
+
+    /// Propagate property uncertainty to yield and risk by Monte Carlo
+    ///
+    /// Runs `n` simulations with properties perturbed per `sigma`, drawn from
+    /// a `StdRng` seeded with `seed`, so the same seed reproduces the same
+    /// summary. A Langmuir isotherm follows the perturbed `langmuir_k`.
+    /// Samples whose simulation fails are left out of the summary.
+    pub fn monte_carlo(
+        &self,
+        n: usize,
+        seed: u64,
+        sigma: &PropertyUncertainty,
+    ) -> MonteCarloSummary {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut yields = Vec::with_capacity(n);
+        let mut risks = Vec::with_capacity(n);
+
+        for _ in 0..n {
+            let mut sample = self.clone();
+            sample.properties = sigma.sample(&self.properties, &mut rng);
+            if let IsothermModel::Langmuir { reference_temp_k, .. } = sample.isotherm {
+                sample.isotherm = IsothermModel::Langmuir {
+                    k: sample.properties.langmuir_k,
+                    reference_temp_k,
+                };
+            }
+            if let Ok(results) = sample.simulate_temperature_swing() {
+                yields.push(results.water_yield_kg);
+                risks.push(results.risk_score);
+            }
+        }
+
+        MonteCarloSummary {
+            samples: yields.len(),
+            water_yield_kg: DistributionSummary::from_values(&mut yields),
+            risk_score: DistributionSummary::from_values(&mut risks),
+        }
+    }
+
+    /// Thermal efficiency under the simulator's `EfficiencyMode`
+    ///
+    /// Zero when no energy is consumed.
+    fn thermal_efficiency(&self, water_yield_kg: f64, energy_kj: f64, peak_temp_k: f64) -> f64 {
+        if energy_kj <= 0.0 {
+            return 0.0;
+        }
+        match self.efficiency_mode {
+            // Water produced (kg) / energy consumed (kJ)
+            EfficiencyMode::WaterPerKj => water_yield_kg / energy_kj,
+            EfficiencyMode::Dimensionless => {
+                self.working_fluid.latent_heat_kj_per_kg * water_yield_kg / energy_kj
+            }
+            EfficiencyMode::SecondLaw => {
+                let carnot = 1.0 - self.conditions.ambient_temp_k / peak_temp_k;
+                self.desorption_heat_kj(water_yield_kg) * carnot / energy_kj
+            }
+        }
+    }
+
+    /// Share of the desorbed vapor liquefied at `condensation_temperature_k`
+    ///
+    /// The desorption stream leaves the bed saturated at `peak_temp_k`, its
+    /// vapor pressure capped at the total pressure. Cooling it to the
+    /// condenser temperature T_c condenses everything above P_sat(T_c):
+    /// 1 − P_sat(T_c) / p_v, floored at zero. 1.0 without a condenser model.
+    fn condensed_fraction(&self, peak_temp_k: f64) -> f64 {
+        let Some(condenser_k) = self.conditions.condensation_temperature_k else {
+            return 1.0;
+        };
+        let total_pa = self.conditions.absolute_pressure_atm() * STANDARD_PRESSURE_PA;
+        let vapor_pa = saturation_pressure(peak_temp_k).min(total_pa);
+        (1.0 - saturation_pressure(condenser_k) / vapor_pa).clamp(0.0, 1.0)
+    }
+
+    /// Water vapor carried through the bed over the adsorption half-cycle (kg)
+    ///
+    /// airflow × t_ads × absolute humidity of the feed at bed pressure; the
+    /// air is only processed while adsorbing. Infinite without an airflow,
+    /// so supply never limits uptake.
+    fn supplied_water_kg(&self) -> f64 {
+        let Some(airflow) = self.conditions.airflow_m3_per_s else {
+            return f64::INFINITY;
+        };
+        let absolute_g_per_m3 = relative_to_absolute_humidity(
+            self.conditions.humidity,
+            self.conditions.ambient_temp_k,
+            self.conditions.absolute_pressure_atm(),
+        );
+        airflow * self.conditions.cycle_time_seconds / 2.0 * absolute_g_per_m3 / G_PER_KG
+    }
+
+    /// Heat of adsorption to release `water_kg` of the working fluid (kJ)
+    ///
+    /// kJ/mol over g/mol gives kJ/g, so × 1000 for kJ per kg of sorbate.
+    fn desorption_heat_kj(&self, water_kg: f64) -> f64 {
+        self.properties.heat_of_adsorption / self.working_fluid.molar_mass_g_per_mol
+            * G_PER_KG
+            * water_kg
+    }
+
+    /// Estimate water uptake fraction from humidity
+    fn estimate_uptake_fraction(&self) -> f64 {
+        self.uptake_fraction_at(self.conditions.ambient_temp_k)
+    }
+
+    /// Equilibrium uptake fraction with the feed vapor at a bed temperature
+    fn uptake_fraction_at(&self, temp_k: f64) -> f64 {
+        self.isotherm.uptake_fraction_at(
+            self.relative_pressure(temp_k),
+            temp_k,
+            self.properties.heat_of_adsorption,
+        )
+    }
+
+    /// Water vapor partial pressure in the bed (Pa)
+    ///
+    /// Feed air at `humidity` is compressed to the altitude-corrected
+    /// `absolute_pressure_atm`, scaling its vapor partial pressure with the
+    /// total pressure. This is the vapor
+    /// concentration `relative_to_absolute_humidity` reports, so uptake
+    /// already tracks absolute rather than relative humidity.
+    fn water_partial_pressure_pa(&self) -> f64 {
+        self.conditions.humidity
+            * saturation_pressure(self.conditions.ambient_temp_k)
+            * self.conditions.absolute_pressure_atm()
+    }
+
+    /// Relative pressure p/p_sat seen by the MOF at the given bed temperature
+    ///
+    /// Capped at 1, where excess vapor condenses rather than adsorbs.
+    fn relative_pressure(&self, temp_k: f64) -> f64 {
+        (self.water_partial_pressure_pa() / saturation_pressure(temp_k)).min(1.0)
+    }
+
+    /// Calculate thermal risk score
+    ///
+    /// Scores the regeneration peak `peak_temp_k`. Once the adsorption
+    /// exotherm alone would push the bed past the safe temperature ratio, the
+    /// hotter of the two is scored instead, so strongly exothermic,
+    /// low-heat-capacity beds carry risk even at a mild regeneration setpoint.
+    fn calculate_risk_score(&self, peak_temp_k: f64) -> f64 {
+        self.risk_with_exotherm(peak_temp_k, self.adsorption_peak_temperature_k())
+    }
+
+    /// `calculate_risk_score` with the exotherm peak already computed
+    fn risk_with_exotherm(&self, peak_temp_k: f64, exotherm_peak: f64) -> f64 {
+        let exotherm_ratio = exotherm_peak / self.properties.thermal_stability_k;
+        if exotherm_ratio >= SAFE_TEMP_RATIO {
+            self.temperature_risk(peak_temp_k.max(exotherm_peak))
+        } else {
+            self.temperature_risk(peak_temp_k)
+        }
+    }
+
+    /// Risk score for a bed at `temp_k`
+    fn temperature_risk(&self, temp_k: f64) -> f64 {
+        // Risk increases as operating temp approaches thermal stability limit
+        let temp_ratio = temp_k / self.properties.thermal_stability_k;
+
+        // Safe operation: temp_ratio < 0.8, risk grows quadratically toward 0.1
+        // Above 0.8: normalized quadratic rising from 0.1 to 1.0 at temp_ratio = 1.0
+        let risk = if temp_ratio < SAFE_TEMP_RATIO {
+            SAFE_RISK_CEILING * (temp_ratio / SAFE_TEMP_RATIO).powi(2)
+        } else {
+            let excess = (temp_ratio - SAFE_TEMP_RATIO) / (1.0 - SAFE_TEMP_RATIO);
+            SAFE_RISK_CEILING + (1.0 - SAFE_RISK_CEILING) * excess.powi(2)
+        };
+
+        risk.clamp(0.0, 1.0)
+    }
+
+    /// Explain the risk score at the current conditions
+    ///
+    /// Reports how close the peak bed temperature comes to the stability
+    /// limit and the most severe `RiskThreshold` it crosses, if any.
+    pub fn risk_report(&self) -> Result<RiskReport, ThermalError> {
+        self.validate_inputs()?;
+        let peak_temp = self.peak_temperature_k();
+        let stability_k = self.properties.thermal_stability_k;
+        let temp_ratio = peak_temp / stability_k;
+        let risk_score = self.calculate_risk_score(peak_temp);
+
+        let threshold_crossed = if temp_ratio >= 1.0 {
+            Some(RiskThreshold::StabilityLimit)
+        } else if risk_score > self.max_allowed_risk {
+            Some(RiskThreshold::MaxAllowedRisk)
+        } else if temp_ratio >= SAFE_TEMP_RATIO {
+            Some(RiskThreshold::SafeTempRatio)
+        } else {
+            None
+        };
+        let verdict = match threshold_crossed {
+            None => "safe",
+            Some(RiskThreshold::SafeTempRatio) => "caution: above the safe temperature ratio",
+            Some(RiskThreshold::MaxAllowedRisk) => "unsafe: risk exceeds the allowed maximum",
+            Some(RiskThreshold::StabilityLimit) => "unsafe: at or beyond the stability limit",
+        };
+        let assessment = format!(
+            "{} (peak {:.1} K is {:.0}% of the {:.1} K stability limit, risk {:.3})",
+            verdict,
+            peak_temp,
+            temp_ratio * 100.0,
+            stability_k,
+            risk_score
+        );
+
+        Ok(RiskReport {
+            risk_score,
+            temp_ratio,
+            stability_margin_k: stability_k - peak_temp,
+            threshold_crossed,
+            assessment,
+        })
+    }
+
+    /// Simulate one cycle and gather every metric into a `FullReport`
+    pub fn full_report(&self) -> Result<FullReport, ThermalError> {
+        let results = self.simulate_temperature_swing()?;
+        let risk = self.risk_report()?;
+        let daily_water_production_kg = self.daily_water_production_kg()?;
+        let limiting_factor = if results.degraded {
+            None
+        } else {
+            Some(self.limiting_factor()?)
+        };
+        let energy = EnergyBreakdown {
+            sensible_kj: results.sensible_energy_kj,
+            adsorption_kj: results.adsorption_energy_kj,
+            recovered_kj: results.recovered_energy_kj,
+            net_kj: results.energy_consumption_kj,
+        };
+        let summary = format!(
+            "FIPS {}: {:.4} kg water for {:.2} kJ at {:.1} K regeneration ({:.3} kWh/L)",
+            self.properties.fips,
+            results.water_yield_kg,
+            results.energy_consumption_kj,
+            self.conditions.regeneration_temp_k,
+            results.energy_per_liter_kwh
+        );
+        Ok(FullReport {
+            results,
+            risk,
+            energy,
+            daily_water_production_kg,
+            limiting_factor,
+            summary,
+        })
+    }
+
+    /// Optimize operating conditions for maximum efficiency
+    pub fn optimize_conditions(&mut self) -> Result<OperatingConditions, ThermalError> {
+        self.optimize_conditions_with(&OptimizationConfig::default(), None)
+    }
+
+    /// Optimize with the default configuration but a custom step (K)
+    ///
+    /// Shorthand for `optimize_conditions_with` setting only `temp_step_k`,
+    /// so fractional steps work too.
+    pub fn optimize_conditions_with_step(
+        &mut self,
+        step_k: f64,
+    ) -> Result<OperatingConditions, ThermalError> {
+        self.optimize_conditions_with(
+            &OptimizationConfig {
+                temp_step_k: step_k,
+                ..Default::default()
+            },
+            None,
+        )
+    }
+
+    /// Choose the cycle time maximizing daily water production
+    ///
+    /// Sweeps `steps` evenly spaced cycle times from `min_s` to `max_s`,
+    /// endpoints included, scoring each by `daily_water_production_kg`. Short
+    /// cycles lose yield to incomplete heating and mass transfer, long ones
+    /// run fewer cycles per day. Points that fail to simulate or exceed
+    /// `max_allowed_risk` are skipped and ties resolve to the shortest cycle;
+    /// if none remain this fails with `OptimizationFailed`. On success `self`
+    /// takes the chosen cycle time and `(cycle_time_seconds, daily_kg)` is
+    /// returned.
+    pub fn optimize_cycle_time(
+        &mut self,
+        min_s: f64,
+        max_s: f64,
+        steps: usize,
+    ) -> Result<(f64, f64), ThermalError> {
+        if !(is_positive(min_s) && min_s < max_s && max_s.is_finite()) {
+            return Err(ThermalError::InvalidParameter(format!(
+                "cycle time range must satisfy 0 < min < max (got {} to {})",
+                min_s, max_s
+            )));
+        }
+        if steps < 2 {
+            return Err(ThermalError::InvalidParameter(format!(
+                "cycle time sweep needs at least 2 steps (got {})",
+                steps
+            )));
+        }
+
+        let spacing = (max_s - min_s) / (steps - 1) as f64;
+        let mut best: Option<(f64, f64)> = None;
+        for i in 0..steps {
+            let cycle_time_seconds = min_s + i as f64 * spacing;
+            let candidate = self.with_conditions(OperatingConditions {
+                cycle_time_seconds,
+                ..self.conditions.clone()
+            });
+            let results = match candidate.simulate_temperature_swing() {
+                Ok(results) => results,
+                Err(err) => {
+                    debug!(
+                        "FIPS {}: skipping cycle {:.0} s: {}",
+                        self.properties.fips, cycle_time_seconds, err
+                    );
+                    continue;
+                }
+            };
+            if results.risk_score > self.max_allowed_risk {
+                continue;
+            }
+            let daily_kg = candidate.daily_production_from(&results);
+            debug!(
+                "FIPS {}: cycle {:.0} s daily {:.4} kg uptake {:.3}",
+                self.properties.fips, cycle_time_seconds, daily_kg, results.uptake_efficiency
+            );
+            if best.is_none_or(|(_, best_kg)| daily_kg > best_kg) {
+                best = Some((cycle_time_seconds, daily_kg));
+            }
+        }
+
+        let (cycle_time_seconds, daily_kg) =
+            best.ok_or(ThermalError::OptimizationFailed { evaluated: steps })?;
+        self.conditions.cycle_time_seconds = cycle_time_seconds;
+        Ok((cycle_time_seconds, daily_kg))
+    }
+
+    /// Optimize regeneration temperature over a configurable sweep
+    ///
+    /// Candidates are simulated in parallel, each on its own temporary
+    /// simulator, so `self` is only updated once the best point is chosen.
+    /// `progress`, if given, is called as each candidate finishes.
+    pub fn optimize_conditions_with(
+        &mut self,
+        config: &OptimizationConfig,
+        progress: Option<Progress<'_>>,
+    ) -> Result<OperatingConditions, ThermalError> {
+        Ok(self.optimize_report(&MaximizeEfficiency, config, progress)?.conditions)
+    }
+
+    /// Like `optimize_conditions_with`, also reporting the evaluation count
+    ///
+    /// Fails with `OptimizationFailed` when no candidate is strictly more
+    /// efficient than zero and below `config.max_risk`; `self` is then unchanged.
+    pub fn optimize_conditions_report(
+        &mut self,
+        config: &OptimizationConfig,
+    ) -> Result<OptimizationReport, ThermalError> {
+        self.optimize_report(&MaximizeEfficiency, config, None)
+    }
+
+    /// Optimize regeneration temperature for a pluggable goal
+    ///
+    /// Sweeps as `optimize_conditions_report` does, keeping the highest
+    /// `objective.score` among candidates that are `objective.feasible` and
+    /// below `config.max_risk`; ties resolve to the lowest temperature. Fails
+    /// with `OptimizationFailed` when no candidate qualifies, leaving `self`
+    /// unchanged.
+    pub fn optimize_with_objective(
+        &mut self,
+        objective: &dyn ObjectiveFn,
+        config: &OptimizationConfig,
+    ) -> Result<OptimizationReport, ThermalError> {
+        self.optimize_report(objective, config, None)
+    }
+
+    /// Sweep, select and apply the best regeneration temperature
+    fn optimize_report(
+        &mut self,
+        objective: &dyn ObjectiveFn,
+        config: &OptimizationConfig,
+        progress: Option<Progress<'_>>,
+    ) -> Result<OptimizationReport, ThermalError> {
+        // Find optimal regeneration temperature
+        // Balance between water yield and energy consumption
+        config.validate()?;
+
+        let fips = self.properties.fips;
+        let candidates = self.sweep_candidates(config);
+        debug!(
+            "FIPS {}: sweeping {} candidates from {:?} K to {:?} K",
+            fips,
+            candidates.len(),
+            candidates.first(),
+            candidates.last()
+        );
+        let evaluated = self.evaluate_candidates(&candidates, progress);
+
+        // Select in sweep order so ties resolve to the lowest temperature
+        let mut best: Option<(f64, SimulationResults)> = None;
+        for (temp, results) in evaluated {
+            let score = objective.score(&results);
+            debug!(
+                "FIPS {}: candidate {:.1} K score {:.3e} risk {:.3}",
+                fips, temp, score, results.risk_score
+            );
+            let qualifies = objective.feasible(&results) && results.risk_score < config.max_risk;
+            if qualifies && best.as_ref().is_none_or(|(_, b)| score > objective.score(b)) {
+                best = Some((temp, results));
+            }
+        }
+
+        let (best_temp, best_results) = best.ok_or_else(|| {
+            debug!("FIPS {}: no candidate satisfied the constraints", fips);
+            ThermalError::OptimizationFailed {
+                evaluated: candidates.len(),
+            }
+        })?;
+        debug!(
+            "FIPS {}: selected {:.1} K (score {:.3e}, risk {:.3})",
+            fips,
+            best_temp,
+            objective.score(&best_results),
+            best_results.risk_score
+        );
+        self.conditions.regeneration_temp_k = best_temp;
+        Ok(OptimizationReport {
+            conditions: self.conditions.clone(),
+            best: best_results,
+            evaluations: candidates.len(),
+        })
+    }
+
+    /// Grid-search regeneration temperature and humidity jointly
+    ///
+    /// Evaluates `steps` evenly spaced values along each range, endpoints
+    /// included, in parallel, and keeps the most efficient point scoring
+    /// strictly below `max_allowed_risk`. Ties resolve to the lowest
+    /// temperature, then the lowest humidity. On success `self` takes the
+    /// chosen conditions, which are returned with their results.
+    pub fn optimize_joint(
+        &mut self,
+        temp_range: (f64, f64),
+        humidity_range: (f64, f64),
+        steps: usize,
+    ) -> Result<(OperatingConditions, SimulationResults), ThermalError> {
+        let (min_temp, max_temp) = temp_range;
+        let (min_humidity, max_humidity) = humidity_range;
+        if !(is_positive(min_temp) && min_temp < max_temp && max_temp.is_finite()) {
+            return Err(ThermalError::InvalidParameter(format!(
+                "temperature range must satisfy 0 < min < max (got {} to {})",
+                min_temp, max_temp
+            )));
+        }
+        if !(0.0 <= min_humidity && min_humidity < max_humidity && max_humidity <= 1.0) {
+            return Err(ThermalError::InvalidParameter(format!(
+                "humidity range must satisfy 0 <= min < max <= 1 (got {} to {})",
+                min_humidity, max_humidity
+            )));
+        }
+        if steps < 2 {
+            return Err(ThermalError::InvalidParameter(format!(
+                "joint grid needs at least 2 steps per axis (got {})",
+                steps
+            )));
+        }
+
+        let temp_spacing = (max_temp - min_temp) / (steps - 1) as f64;
+        let humidity_spacing = (max_humidity - min_humidity) / (steps - 1) as f64;
+        let grid: Vec<(f64, f64)> = (0..steps)
+            .flat_map(|i| {
+                (0..steps).map(move |j| {
+                    (min_temp + i as f64 * temp_spacing, min_humidity + j as f64 * humidity_spacing)
+                })
+            })
+            .collect();
+
+        let evaluated: Vec<(OperatingConditions, SimulationResults)> = grid
+            .par_iter()
+            .filter_map(|&(regeneration_temp_k, humidity)| {
+                let conditions = OperatingConditions {
+                    regeneration_temp_k,
+                    humidity,
+                    ..self.conditions.clone()
+                };
+                self.with_conditions(conditions.clone())
+                    .simulate_temperature_swing()
+                    .ok()
+                    .map(|results| (conditions, results))
+            })
+            .collect();
+
+        // Select in grid order so ties resolve to the lowest temperature
+        let mut best: Option<(OperatingConditions, SimulationResults)> = None;
+        for (conditions, results) in evaluated {
+            let best_efficiency = best.as_ref().map_or(0.0, |(_, b)| b.thermal_efficiency);
+            if results.thermal_efficiency > best_efficiency
+                && results.risk_score < self.max_allowed_risk
+            {
+                best = Some((conditions, results));
+            }
+        }
+
+        let (conditions, results) = best.ok_or(ThermalError::OptimizationFailed {
+            evaluated: grid.len(),
+        })?;
+        debug!(
+            "FIPS {}: joint optimum {:.1} K at humidity {:.2} (efficiency {:.3e})",
+            self.properties.fips,
+            conditions.regeneration_temp_k,
+            conditions.humidity,
+            results.thermal_efficiency
+        );
+        self.conditions = conditions.clone();
+        Ok((conditions, results))
+    }
+
+    /// Yield-vs-energy Pareto front over the regeneration temperature sweep
+    ///
+    /// Keeps candidates below `config.max_risk` that no other candidate beats
+    /// on both water yield (higher) and energy (lower). Sorted by ascending
+    /// energy, then ascending yield, so the order never depends on how the
+    /// parallel sweep was scheduled.
+    pub fn pareto_front(
+        &self,
+        config: &OptimizationConfig,
+    ) -> Result<Vec<SimulationResults>, ThermalError> {
+        config.validate()?;
+
+        let feasible: Vec<SimulationResults> = self
+            .evaluate_sweep(config)
+            .into_iter()
+            .map(|(_, results)| results)
+            .filter(|results| results.risk_score < config.max_risk)
+            .collect();
+
+        let mut front: Vec<SimulationResults> = feasible
+            .iter()
+            .filter(|candidate| {
+                !feasible
+                    .iter()
+                    .any(|other| yield_energy_dominates(other, candidate))
+            })
+            .cloned()
+            .collect();
+        front.sort_by(|a, b| {
+            a.energy_consumption_kj
+                .total_cmp(&b.energy_consumption_kj)
+                .then(a.water_yield_kg.total_cmp(&b.water_yield_kg))
+        });
+        Ok(front)
+    }
+
+    /// Regeneration temperatures covered by a sweep configuration
+    ///
+    /// Steps of `temp_step_k` from 30 K above ambient, staying below
+    /// `safety_margin` × `thermal_stability_k`.
+    fn sweep_candidates(&self, config: &OptimizationConfig) -> Vec<f64> {
+        // Bounds stay fractional: truncating them drops the top of the range
+        let min_temp = self.conditions.ambient_temp_k + 30.0;
+        let max_temp = self.properties.thermal_stability_k * config.safety_margin;
+        let steps = ((max_temp - min_temp) / config.temp_step_k).ceil().max(0.0) as usize;
+        (0..steps)
+            .map(|i| min_temp + i as f64 * config.temp_step_k)
+            .collect()
+    }
+
+    /// Simulate every sweep candidate in parallel, in sweep order
+    fn evaluate_sweep(&self, config: &OptimizationConfig) -> Vec<(f64, SimulationResults)> {
+        self.evaluate_candidates(&self.sweep_candidates(config), None)
+    }
+
+    /// Simulate the given regeneration temperatures in parallel, in order
+    ///
+    /// Each candidate runs on its own temporary simulator; candidates whose
+    /// simulation fails are skipped.
+    fn evaluate_candidates(
+        &self,
+        candidates: &[f64],
+        progress: Option<Progress<'_>>,
+    ) -> Vec<(f64, SimulationResults)> {
+        let completed = AtomicUsize::new(0);
+        candidates
+            .par_iter()
+            .filter_map(|&temp| {
+                let conditions = OperatingConditions {
+                    regeneration_temp_k: temp,
+                    ..self.conditions.clone()
+                };
+                let results = self.with_conditions(conditions).simulate_temperature_swing();
+                if let Some(progress) = progress {
+                    let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
+                    progress(done, candidates.len());
+                }
+                results.ok().map(|results| (temp, results))
+            })
+            .collect()
+    }
+
+    /// Optimize regeneration temperature by golden-section search
+    ///
+    /// Assumes efficiency is unimodal in temperature over the default sweep
+    /// range, needing only logarithmically many simulations. If the converged
+    /// point violates the risk constraint, falls back to the grid sweep.
+    pub fn optimize_conditions_golden(&mut self) -> Result<OperatingConditions, ThermalError> {
+        let config = OptimizationConfig::default();
+        let efficiency_at = |temp: f64| {
+            let conditions = OperatingConditions {
+                regeneration_temp_k: temp,
+                ..self.conditions.clone()
+            };
+            self.with_conditions(conditions)
+                .simulate_temperature_swing()
+                .map_or(f64::NEG_INFINITY, |results| results.thermal_efficiency)
+        };
+
+        let inv_phi = (5.0f64.sqrt() - 1.0) / 2.0;
+        let mut lower = self.conditions.ambient_temp_k + 30.0;
+        let mut upper = self.properties.thermal_stability_k * config.safety_margin;
+        let mut left = upper - inv_phi * (upper - lower);
+        let mut right = lower + inv_phi * (upper - lower);
+        let mut left_eff = efficiency_at(left);
+        let mut right_eff = efficiency_at(right);
+
+        let mut iterations = 0;
+        while upper - lower > GOLDEN_TOLERANCE_K && iterations < MAX_GOLDEN_ITERATIONS {
+            iterations += 1;
+            if left_eff > right_eff {
+                upper = right;
+                right = left;
+                right_eff = left_eff;
+                left = upper - inv_phi * (upper - lower);
+                left_eff = efficiency_at(left);
+            } else {
+                lower = left;
+                left = right;
+                left_eff = right_eff;
+                right = lower + inv_phi * (upper - lower);
+                right_eff = efficiency_at(right);
+            }
+        }
+
+        let best_temp = (lower + upper) / 2.0;
+        let candidate = self.with_conditions(OperatingConditions {
+            regeneration_temp_k: best_temp,
+            ..self.conditions.clone()
+        });
+        match candidate.simulate_temperature_swing() {
+            Ok(results) if results.risk_score < config.max_risk => {
+                self.conditions.regeneration_temp_k = best_temp;
+                Ok(self.conditions.clone())
+            }
+            // Not unimodal or not safe: fall back to the exhaustive grid
+            _ => self.optimize_conditions_with(&config, None),
+        }
+    }
+}
+
+/// Load placeholder MOF properties for a FIPS code
+///
+/// Use `load_mof_properties_from_csv` when a property dataset is available, or
+/// `MOFThermalProperties::default_for_class` when only the framework family
+/// is known.
+pub fn load_mof_properties(fips: u32) -> Result<MOFThermalProperties, ThermalError> {
+    // Placeholder: no dataset supplied, return default properties
+    debug!("FIPS {}: no dataset supplied, using placeholder properties", fips);
+    Ok(MOFThermalProperties {
+        fips,
+        ..Default::default()
+    })
+}
+
+/// Whether `a` beats `b` on yield (higher) and energy (lower), strictly on one
+fn yield_energy_dominates(a: &SimulationResults, b: &SimulationResults) -> bool {
+    a.water_yield_kg >= b.water_yield_kg
+        && a.energy_consumption_kj <= b.energy_consumption_kj
+        && (a.water_yield_kg > b.water_yield_kg
+            || a.energy_consumption_kj < b.energy_consumption_kj)
+}
+
+/// Simulate two MOFs under the same conditions and pick the dominant one
+///
+/// Returns `None` when neither result dominates the other (see
+/// `SimulationResults::dominates`), i.e. the choice is a trade-off.
+pub fn compare_mofs<'a>(
+    a: &'a MOFThermalProperties,
+    b: &'a MOFThermalProperties,
+    conditions: &OperatingConditions,
+) -> Result<Option<&'a MOFThermalProperties>, ThermalError> {
+    let simulate = |properties: &MOFThermalProperties| {
+        ThermalFluidSimulator::new(properties.clone(), conditions.clone())
+            .simulate_temperature_swing()
+    };
+    let (a_results, b_results) = (simulate(a)?, simulate(b)?);
+
+    if a_results.dominates(&b_results) {
+        Ok(Some(a))
+    } else if b_results.dominates(&a_results) {
+        Ok(Some(b))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Simulate one temperature-swing cycle per FIPS code
+///
+/// Properties come from `store`, so the dataset is parsed once for the whole
+/// batch. Output preserves input order, and a failure for one FIPS code is
+/// recorded in its entry without aborting the rest of the batch. `progress`,
+/// if given, is called after each FIPS code is simulated.
+pub fn simulate_batch(
+    store: &MofPropertyStore,
+    fips_list: &[u32],
+    conditions: &OperatingConditions,
+    progress: Option<Progress<'_>>,
+) -> Vec<(u32, Result<SimulationResults, ThermalError>)> {
+    fips_list
+        .iter()
+        .enumerate()
+        .map(|(i, &fips)| {
+            let results = simulate_fips(store, fips, conditions);
+            if let Some(progress) = progress {
+                progress(i + 1, fips_list.len());
+            }
+            (fips, results)
+        })
+        .collect()
+}
+
+/// Parallel variant of `simulate_batch` for large FIPS lists
+pub fn simulate_batch_parallel(
+    store: &MofPropertyStore,
+    fips_list: &[u32],
+    conditions: &OperatingConditions,
+) -> Vec<(u32, Result<SimulationResults, ThermalError>)> {
+    fips_list
+        .par_iter()
+        .map(|&fips| (fips, simulate_fips(store, fips, conditions)))
+        .collect()
+}
+
+/// Look up properties for a FIPS code and run a single swing simulation
+fn simulate_fips(
+    store: &MofPropertyStore,
+    fips: u32,
+    conditions: &OperatingConditions,
+) -> Result<SimulationResults, ThermalError> {
+    let properties = store.get(fips).ok_or(ThermalError::FipsNotFound(fips))?;
+    ThermalFluidSimulator::new(properties.clone(), conditions.clone())
+        .simulate_temperature_swing()
+}
+
+/// Percent differences from a baseline, from `SimulationResults::compare_to_baseline`
+///
+/// `None` where the baseline value is zero and a percentage is undefined.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ComparisonReport {
+    pub water_yield_pct: Option<f64>,
+    pub energy_consumption_pct: Option<f64>,
+    pub thermal_efficiency_pct: Option<f64>,
+    pub risk_score_pct: Option<f64>,
+}
+
+/// Regional totals from `aggregate_region`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegionSummary {
+    pub county_count: usize,          // Counties simulated
+    pub water_yield_kg: f64,          // Sum over counties
+    pub energy_consumption_kj: f64,   // Sum over counties
+    pub mean_risk_score: f64,         // 0.0 when no county was simulated
+    pub worst_risk_fips: Option<u32>, // Highest-risk county; first listed wins ties
+    pub worst_risk_score: f64,
+    pub missing: Vec<u32>,            // Requested FIPS codes absent from the store
+}
+
+/// Simulate every county in `fips_list` and combine them into regional totals
+///
+/// Counties are simulated in parallel, but their results are summed serially
+/// in `fips_list` order, so repeated runs give bit-identical totals however
+/// rayon schedules the work. FIPS codes not in `store` are skipped and listed
+/// in `missing`; any other simulation failure aborts the aggregation, with
+/// the first failing county in list order reported.
+pub fn aggregate_region(
+    store: &MofPropertyStore,
+    fips_list: &[u32],
+    conditions: &OperatingConditions,
+) -> Result<RegionSummary, ThermalError> {
+    let mut summary = RegionSummary {
+        county_count: 0,
+        water_yield_kg: 0.0,
+        energy_consumption_kj: 0.0,
+        mean_risk_score: 0.0,
+        worst_risk_fips: None,
+        worst_risk_score: 0.0,
+        missing: Vec::new(),
+    };
+    let mut total_risk = 0.0;
+
+    // Fixed fold order: floating-point addition is not associative
+    for (fips, results) in simulate_batch_parallel(store, fips_list, conditions) {
+        let results = match results {
+            Err(ThermalError::FipsNotFound(_)) => {
+                summary.missing.push(fips);
+                continue;
+            }
+            results => results?,
+        };
+        summary.county_count += 1;
+        summary.water_yield_kg += results.water_yield_kg;
+        summary.energy_consumption_kj += results.energy_consumption_kj;
+        total_risk += results.risk_score;
+        if summary.worst_risk_fips.is_none() || results.risk_score > summary.worst_risk_score {
+            summary.worst_risk_fips = Some(fips);
+            summary.worst_risk_score = results.risk_score;
+        }
+    }
+
+    if summary.county_count > 0 {
+        summary.mean_risk_score = total_risk / summary.county_count as f64;
+    }
+    Ok(summary)
+}
+
+/// MOF properties parsed once and indexed by FIPS code
+///
+/// Batch runs look up every county here instead of re-reading the CSV.
+#[derive(Debug, Clone, Default)]
+pub struct MofPropertyStore {
+    properties: HashMap<u32, MOFThermalProperties>,
+}
+
+impl MofPropertyStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a property CSV file into a store
+    ///
+    /// Format rules match `load_mof_properties_from_csv`.
+    pub fn from_csv<P: AsRef<Path>>(path: P) -> Result<Self, ThermalError> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    /// Parse property CSV data from any source into a store
+    ///
+    /// Every row is validated. If a FIPS code repeats, the first row wins.
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, ThermalError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(reader);
+
+        // Map each required column to its position in the header
+        let headers = reader.headers()?.clone();
+        let mut columns = [0usize; PROPERTY_COLUMNS.len()];
+        for (slot, name) in columns.iter_mut().zip(PROPERTY_COLUMNS) {
+            *slot = headers
+                .iter()
+                .position(|header| header == name)
+                .ok_or_else(|| {
+                    ThermalError::CsvParse(format!("missing required column '{}'", name))
+                })?;
+        }
+        let optional = OPTIONAL_PROPERTY_COLUMNS
+            .map(|name| headers.iter().position(|header| header == name));
+
+        let mut store = Self::new();
+        for record in reader.records() {
+            let record = record?;
+            let line = record.position().map_or(0, |pos| pos.line());
+            let properties = parse_property_record(&record, &columns, &optional, line)?;
+            store.properties.entry(properties.fips).or_insert(properties);
+        }
+
+        Ok(store)
+    }
+
+    /// Add or replace the entry for `properties.fips`
+    pub fn insert(&mut self, properties: MOFThermalProperties) {
+        self.properties.insert(properties.fips, properties);
+    }
+
+    /// Properties for a FIPS code, if present
+    pub fn get(&self, fips: u32) -> Option<&MOFThermalProperties> {
+        self.properties.get(&fips)
+    }
+
+    /// Number of FIPS codes in the store
+    pub fn len(&self) -> usize {
+        self.properties.len()
+    }
+
+    /// Whether the store has no entries
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+    }
+}
+
+/// CSV columns required for MOF thermal properties, one per struct field
+const PROPERTY_COLUMNS: [&str; 8] = [
+    "fips",
+    "thermal_conductivity",
+    "specific_heat",
+    "density",
+    "thermal_stability_k",
+    "heat_of_adsorption",
+    "langmuir_k",
+    "max_uptake_g_per_g",
+];
+
+/// CSV columns that may be omitted, taking the serde default when absent
+const OPTIONAL_PROPERTY_COLUMNS: [&str; 1] = ["mass_transfer_coeff"];
+
+/// Load MOF properties for a FIPS code from a CSV file
+///
+/// The file must have a header row naming every required
+/// `MOFThermalProperties` field; `OPTIONAL_PROPERTY_COLUMNS` may be omitted.
+/// Every row is validated, so a malformed row anywhere in the file is an error.
+pub fn load_mof_properties_from_csv<P: AsRef<Path>>(
+    path: P,
+    fips: u32,
+) -> Result<MOFThermalProperties, ThermalError> {
+    load_mof_properties_from_reader(File::open(path)?, fips)
+}
+
+/// Load MOF properties for a FIPS code from any CSV source
+///
+/// Accepts in-memory buffers or network responses as well as files; the
+/// format rules match `load_mof_properties_from_csv`.
+pub fn load_mof_properties_from_reader<R: Read>(
+    reader: R,
+    fips: u32,
+) -> Result<MOFThermalProperties, ThermalError> {
+    let store = MofPropertyStore::from_reader(reader)?;
+    debug!("FIPS {}: looking up properties among {} CSV rows", fips, store.len());
+    let mut properties = store.properties;
+    properties.remove(&fips).ok_or(ThermalError::FipsNotFound(fips))
+}
+
+/// Properties for `fips`, interpolated from `neighbors` when it is missing
+///
+/// An exact match in `store` is returned as-is. Otherwise every property is
+/// the arithmetic mean over the neighbor FIPS codes present in the store,
+/// tagged with `fips`. This is a spatial approximation, not a measurement:
+/// it assumes neighboring counties deploy similar MOFs. Fails with
+/// `FipsNotFound` if neither `fips` nor any neighbor is in the store.
+pub fn load_mof_properties_interpolated(
+    store: &MofPropertyStore,
+    fips: u32,
+    neighbors: &[u32],
+) -> Result<MOFThermalProperties, ThermalError> {
+    if let Some(properties) = store.get(fips) {
+        return Ok(properties.clone());
+    }
+
+    let found: Vec<&MOFThermalProperties> =
+        neighbors.iter().filter_map(|&neighbor| store.get(neighbor)).collect();
+    if found.is_empty() {
+        return Err(ThermalError::FipsNotFound(fips));
+    }
+    debug!(
+        "FIPS {}: interpolating properties from {} of {} neighbors",
+        fips,
+        found.len(),
+        neighbors.len()
+    );
+
+    let mean = |field: fn(&MOFThermalProperties) -> f64| {
+        found.iter().map(|p| field(p)).sum::<f64>() / found.len() as f64
+    };
+    let properties = MOFThermalProperties {
+        fips,
+        thermal_conductivity: mean(|p| p.thermal_conductivity),
+        specific_heat: mean(|p| p.specific_heat),
+        density: mean(|p| p.density),
+        thermal_stability_k: mean(|p| p.thermal_stability_k),
+        heat_of_adsorption: mean(|p| p.heat_of_adsorption),
+        langmuir_k: mean(|p| p.langmuir_k),
+        max_uptake_g_per_g: mean(|p| p.max_uptake_g_per_g),
+        mass_transfer_coeff: mean(|p| p.mass_transfer_coeff),
+    };
+    properties.validate()?;
+    Ok(properties)
+}
+
+/// Parse a finite number for `column`
+///
+/// `inf`, `NaN` and literals that overflow `f64` (e.g. `1e400`) are rejected
+/// here so they surface as `CsvParse` instead of poisoning a simulation.
+fn parse_finite(value: &str, column: &str, line: u64) -> Result<f64, ThermalError> {
+    match value.parse::<f64>() {
+        Ok(number) if number.is_finite() => Ok(number),
+        Ok(_) => Err(ThermalError::CsvParse(format!(
+            "line {}: non-finite number '{}' for '{}'",
+            line, value, column
+        ))),
+        Err(_) => Err(ThermalError::CsvParse(format!(
+            "line {}: invalid number '{}' for '{}'",
+            line, value, column
+        ))),
+    }
+}
+
+/// Parse one CSV record into MOF properties, reporting the line on failure
+///
+/// Optional columns that are absent from the header or blank in the record
+/// take their serde defaults.
+fn parse_property_record(
+    record: &csv::StringRecord,
+    columns: &[usize; PROPERTY_COLUMNS.len()],
+    optional: &[Option<usize>; OPTIONAL_PROPERTY_COLUMNS.len()],
+    line: u64,
+) -> Result<MOFThermalProperties, ThermalError> {
+    let field = |i: usize| -> Result<&str, ThermalError> {
+        match record.get(columns[i]) {
+            Some(value) if !value.is_empty() => Ok(value),
+            _ => Err(ThermalError::CsvParse(format!(
+                "line {}: missing value for '{}'",
+                line, PROPERTY_COLUMNS[i]
+            ))),
+        }
+    };
+    let number = |i: usize| -> Result<f64, ThermalError> {
+        parse_finite(field(i)?, PROPERTY_COLUMNS[i], line)
+    };
+
+    let fips_value = field(0)?;
+    let fips = fips_value.parse::<u32>().map_err(|_| {
+        ThermalError::CsvParse(format!("line {}: invalid FIPS code '{}'", line, fips_value))
+    })?;
+
+    let optional_number = |i: usize, default: f64| -> Result<f64, ThermalError> {
+        match optional[i].and_then(|column| record.get(column)) {
+            Some(value) if !value.is_empty() => {
+                parse_finite(value, OPTIONAL_PROPERTY_COLUMNS[i], line)
+            }
+            _ => Ok(default),
+        }
+    };
+
+    Ok(MOFThermalProperties {
+        fips,
+        thermal_conductivity: number(1)?,
+        specific_heat: number(2)?,
+        density: number(3)?,
+        thermal_stability_k: number(4)?,
+        heat_of_adsorption: number(5)?,
+        langmuir_k: number(6)?,
+        max_uptake_g_per_g: number(7)?,
+        mass_transfer_coeff: optional_number(0, default_mass_transfer_coeff())?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thermal_simulation() {
+        let properties = MOFThermalProperties {
+            fips: 1005,
+            thermal_conductivity: 0.5,
+            specific_heat: 1000.0,
+            density: 600.0,
+            thermal_stability_k: 573.0,
+            heat_of_adsorption: 45.0,
+            langmuir_k: 5.0,
+            max_uptake_g_per_g: 0.3,
+            mass_transfer_coeff: 0.01,
+        };
+
+        let conditions = OperatingConditions {
+            ambient_temp_k: 298.0,
+            regeneration_temp_k: 373.0,
+            humidity: 0.4,
+            pressure_atm: 1.0,
+            cycle_time_seconds: 3600.0,
+            bed_mass_kg: 1.0,
+            heat_recovery_fraction: 0.0,
+            capacity_decay_per_cycle: 0.0,
+            altitude_m: 0.0,
+            condensation_temperature_k: None,
+            airflow_m3_per_s: None,
+            desorption_fraction: 1.0,
+        };
+
+        let simulator = ThermalFluidSimulator::new(properties, conditions);
+        let results = simulator.simulate_temperature_swing().unwrap();
+
+        assert!(results.water_yield_kg > 0.0);
+        assert!(results.thermal_efficiency > 0.0);
+        assert!(results.risk_score < 0.5);
+    }
+
+    #[test]
+    fn test_optimization() {
+        let properties = load_mof_properties(1005).unwrap();
+        let conditions = OperatingConditions {
+            ambient_temp_k: 298.0,
+            regeneration_temp_k: 373.0,
+            humidity: 0.4,
+            pressure_atm: 1.0,
+            cycle_time_seconds: 3600.0,
+            bed_mass_kg: 1.0,
+            heat_recovery_fraction: 0.0,
+            capacity_decay_per_cycle: 0.0,
+            altitude_m: 0.0,
+            condensation_temperature_k: None,
+            airflow_m3_per_s: None,
+            desorption_fraction: 1.0,
+        };
+
+        let mut simulator = ThermalFluidSimulator::new(properties, conditions);
+        let optimized = simulator.optimize_conditions().unwrap();
+
+        assert!(optimized.regeneration_temp_k > 298.0);
+        assert!(optimized.regeneration_temp_k < 573.0 * 0.85);
+    }
+
+    #[test]
+    fn test_uptake_follows_material_isotherm() {
+        let conditions = OperatingConditions::default();
+
+        let baseline =
+            ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions.clone())
+                .simulate_temperature_swing()
+                .unwrap();
+
+        let high_capacity = MOFThermalProperties {
+            max_uptake_g_per_g: 0.6,
+            ..Default::default()
+        };
+        let doubled = ThermalFluidSimulator::new(high_capacity, conditions.clone())
+            .simulate_temperature_swing()
+            .unwrap();
+        assert!((doubled.water_yield_kg - 2.0 * baseline.water_yield_kg).abs() < 1e-12);
+
+        let weak_binding = MOFThermalProperties {
+            langmuir_k: 1.0,
+            ..Default::default()
+        };
+        let weaker = ThermalFluidSimulator::new(weak_binding, conditions)
+            .simulate_temperature_swing()
+            .unwrap();
+        assert!(weaker.water_yield_kg < baseline.water_yield_kg);
+    }
+
+    /// Logger that keeps every record so tests can inspect diagnostics
+    struct CapturingLogger {
+        lines: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl log::Log for CapturingLogger {
+        fn enabled(&self, _metadata: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            let line = format!("{} {}", record.level(), record.args());
+            self.lines.lock().unwrap().push(line);
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CapturingLogger = CapturingLogger {
+        lines: std::sync::Mutex::new(Vec::new()),
+    };
+
+    /// Install the capturing logger (once per test binary) and return it
+    fn capture_logs() -> &'static CapturingLogger {
+        static INSTALL: std::sync::Once = std::sync::Once::new();
+        INSTALL.call_once(|| {
+            log::set_logger(&LOGGER).unwrap();
+            log::set_max_level(log::LevelFilter::Trace);
+        });
+        &LOGGER
+    }
+
+    /// Assert every field of two results agrees within a relative tolerance
+    ///
+    /// Floats match when equal (covering 0 and infinity) or within
+    /// `rel_tol` of the larger magnitude; flags, mode and version must match
+    /// exactly. Destructuring keeps this exhaustive as fields are added.
+    pub(crate) fn assert_results_close(
+        a: &SimulationResults,
+        b: &SimulationResults,
+        rel_tol: f64,
+    ) {
+        let SimulationResults {
+            water_yield_kg,
+            uptake_efficiency,
+            mass_transfer_limited,
+            energy_consumption_kj,
+            sensible_energy_kj,
+            adsorption_energy_kj,
+            recovered_energy_kj,
+            max_temperature_k,
+            stability_margin_k,
+            cooling_time_seconds,
+            insufficient_cycle_time,
+            lumped_model_invalid,
+            degraded,
+            condensed_fraction,
+            thermal_efficiency,
+            efficiency_mode,
+            coefficient_of_performance,
+            energy_per_liter_kwh,
+            risk_score,
+            schema_version,
+        } = a;
+
+        let floats = [
+            ("water_yield_kg", *water_yield_kg, b.water_yield_kg),
+            ("uptake_efficiency", *uptake_efficiency, b.uptake_efficiency),
+            ("energy_consumption_kj", *energy_consumption_kj, b.energy_consumption_kj),
+            ("sensible_energy_kj", *sensible_energy_kj, b.sensible_energy_kj),
+            ("adsorption_energy_kj", *adsorption_energy_kj, b.adsorption_energy_kj),
+            ("recovered_energy_kj", *recovered_energy_kj, b.recovered_energy_kj),
+            ("max_temperature_k", *max_temperature_k, b.max_temperature_k),
+            ("stability_margin_k", *stability_margin_k, b.stability_margin_k),
+            ("cooling_time_seconds", *cooling_time_seconds, b.cooling_time_seconds),
+            ("thermal_efficiency", *thermal_efficiency, b.thermal_efficiency),
+            (
+                "coefficient_of_performance",
+                *coefficient_of_performance,
+                b.coefficient_of_performance,
+            ),
+            ("energy_per_liter_kwh", *energy_per_liter_kwh, b.energy_per_liter_kwh),
+            ("risk_score", *risk_score, b.risk_score),
+            ("condensed_fraction", *condensed_fraction, b.condensed_fraction),
+        ];
+        for (name, x, y) in floats {
+            let close = x == y || (x - y).abs() <= rel_tol * x.abs().max(y.abs());
+            assert!(close, "{} differs: {} vs {} (rel_tol {})", name, x, y, rel_tol);
+        }
+        assert_eq!(*mass_transfer_limited, b.mass_transfer_limited, "mass_transfer_limited");
+        assert_eq!(*insufficient_cycle_time, b.insufficient_cycle_time, "insufficient_cycle_time");
+        assert_eq!(*lumped_model_invalid, b.lumped_model_invalid, "lumped_model_invalid");
+        assert_eq!(*degraded, b.degraded, "degraded");
+        assert_eq!(*efficiency_mode, b.efficiency_mode, "efficiency_mode");
+        assert_eq!(*schema_version, b.schema_version, "schema_version");
+    }
+
+    pub(crate) fn fixture(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join(name)
+    }
+
+    #[test]
+    fn test_load_properties_from_csv() {
+        let properties = load_mof_properties_from_csv(fixture("mof_properties.csv"), 1003).unwrap();
+
+        assert_eq!(properties.fips, 1003);
+        assert_eq!(properties.thermal_conductivity, 0.52);
+        assert_eq!(properties.specific_heat, 1020.0);
+        assert_eq!(properties.density, 610.0);
+        assert_eq!(properties.thermal_stability_k, 593.0);
+        assert_eq!(properties.heat_of_adsorption, 47.5);
+        assert_eq!(properties.langmuir_k, 5.5);
+        assert_eq!(properties.max_uptake_g_per_g, 0.27);
+    }
+
+    #[test]
+    fn test_load_properties_missing_fips() {
+        let err = load_mof_properties_from_csv(fixture("mof_properties.csv"), 9999).unwrap_err();
+        assert!(matches!(err, ThermalError::FipsNotFound(9999)));
+    }
+
+    #[test]
+    fn test_load_properties_missing_file() {
+        let err = load_mof_properties_from_csv(fixture("does_not_exist.csv"), 1001).unwrap_err();
+        assert!(matches!(err, ThermalError::Io(_)));
+    }
+
+    #[test]
+    fn test_load_properties_malformed_rows() {
+        let err = load_mof_properties_from_csv(fixture("mof_properties_missing_column.csv"), 1001)
+            .unwrap_err();
+        assert!(matches!(err, ThermalError::CsvParse(_)));
+        assert!(err.to_string().contains("missing required column 'max_uptake_g_per_g'"));
+
+        let err = load_mof_properties_from_csv(fixture("mof_properties_bad_value.csv"), 1001)
+            .unwrap_err();
+        assert!(err.to_string().contains("line 3"));
+        assert!(err.to_string().contains("'not-a-number' for 'specific_heat'"));
+
+        let err = load_mof_properties_from_csv(fixture("mof_properties_short_row.csv"), 1001)
+            .unwrap_err();
+        assert!(err.to_string().contains("line 3: missing value for 'density'"));
+    }
+
+    fn assert_invalid(conditions: OperatingConditions, expected: &str) {
+        match conditions.validate() {
+            Err(ThermalError::InvalidConditions(msg)) => {
+                assert!(msg.contains(expected), "'{}' missing from '{}'", expected, msg)
+            }
+            other => panic!("expected InvalidConditions, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_accepts_default_conditions() {
+        assert!(OperatingConditions::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_each_constraint() {
+        assert_invalid(
+            OperatingConditions { ambient_temp_k: -5.0, ..Default::default() },
+            "ambient_temp_k must be positive",
+        );
+        assert_invalid(
+            OperatingConditions { regeneration_temp_k: -1.0, ..Default::default() },
+            "regeneration_temp_k must be positive",
+        );
+        assert_invalid(
+            OperatingConditions { regeneration_temp_k: 290.0, ..Default::default() },
+            "must exceed ambient_temp_k",
+        );
+        assert_invalid(
+            OperatingConditions { humidity: 1.2, ..Default::default() },
+            "humidity must be within [0, 1]",
+        );
+        assert_invalid(
+            OperatingConditions { humidity: -0.1, ..Default::default() },
+            "humidity must be within [0, 1]",
+        );
+        assert_invalid(
+            OperatingConditions { pressure_atm: -1.0, ..Default::default() },
+            "pressure_atm must be positive",
+        );
+        assert_invalid(
+            OperatingConditions { cycle_time_seconds: 0.0, ..Default::default() },
+            "cycle_time_seconds must be positive",
+        );
+    }
+
+    #[test]
+    fn test_simulation_reports_all_violations() {
+        let conditions = OperatingConditions {
+            humidity: 1.5,
+            pressure_atm: 0.0,
+            ..Default::default()
+        };
+        let simulator = ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions);
+
+        match simulator.simulate_temperature_swing() {
+            Err(ThermalError::InvalidConditions(msg)) => {
+                assert!(msg.contains("humidity"));
+                assert!(msg.contains("pressure_atm"));
+            }
+            other => panic!("expected InvalidConditions, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_multi_cycle_degradation() {
+        let conditions = OperatingConditions {
+            capacity_decay_per_cycle: 0.01,
+            ..Default::default()
+        };
+        let simulator = ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions);
+
+        let single = simulator.simulate_temperature_swing().unwrap();
+        let cycles = simulator.simulate_cycles(50).unwrap();
+        assert_eq!(cycles.len(), 50);
+        assert!((cycles[0].water_yield_kg - single.water_yield_kg).abs() < 1e-12);
+
+        for pair in cycles.windows(2) {
+            assert!(pair[1].water_yield_kg <= pair[0].water_yield_kg);
+        }
+        assert!(cycles[49].water_yield_kg < cycles[0].water_yield_kg);
+
+        // Cumulative water over the run is the geometric series of per-cycle yields
+        let cumulative: f64 = cycles.iter().map(|r| r.water_yield_kg).sum();
+        let expected = single.water_yield_kg * (1.0 - 0.99f64.powi(50)) / 0.01;
+        assert!((cumulative - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_decay_outside_range_rejected() {
+        assert_invalid(
+            OperatingConditions { capacity_decay_per_cycle: 1.0, ..Default::default() },
+            "capacity_decay_per_cycle must be within [0, 1)",
+        );
+    }
+
+    #[test]
+    fn test_builder_defaults_and_overrides() {
+        let conditions = OperatingConditions::builder()
+            .regeneration_temp_k(393.0)
+            .humidity(0.6)
+            .build()
+            .unwrap();
+
+        assert_eq!(conditions.ambient_temp_k, 298.0);
+        assert_eq!(conditions.regeneration_temp_k, 393.0);
+        assert_eq!(conditions.humidity, 0.6);
+        assert_eq!(conditions.pressure_atm, 1.0);
+        assert_eq!(conditions.cycle_time_seconds, 3600.0);
+    }
+
+    #[test]
+    fn test_builder_validates() {
+        let err = OperatingConditions::builder()
+            .ambient_temp_k(400.0)
+            .regeneration_temp_k(350.0)
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, ThermalError::InvalidConditions(_)));
+    }
+
+    /// Reference serial sweep mirroring the optimization loop
+    ///
+    /// Walks fractional bounds in f64 steps, as the parallel sweep does, and
+    /// returns the optimum along with every temperature visited.
+    fn serial_optimum(simulator: &mut ThermalFluidSimulator, step_k: f64) -> (f64, Vec<f64>) {
+        let mut best_efficiency = 0.0;
+        let mut best_temp = simulator.conditions.regeneration_temp_k;
+        let min_temp = simulator.conditions.ambient_temp_k + 30.0;
+        let max_temp = simulator.properties.thermal_stability_k * 0.85;
+
+        let mut visited = Vec::new();
+        let mut temp = min_temp;
+        while temp < max_temp {
+            visited.push(temp);
+            simulator.conditions.regeneration_temp_k = temp;
+            if let Ok(results) = simulator.simulate_temperature_swing() {
+                if results.thermal_efficiency > best_efficiency && results.risk_score < 0.5 {
+                    best_efficiency = results.thermal_efficiency;
+                    best_temp = temp;
+                }
+            }
+            temp = min_temp + visited.len() as f64 * step_k;
+        }
+        (best_temp, visited)
+    }
+
+    #[test]
+    fn test_parallel_optimization_matches_serial() {
+        // 0.85 × 471.65 K = 400.9 K and 298.15 K ambient leave both bounds fractional
+        let fractional = (
+            MOFThermalProperties::default().with_thermal_stability_k(471.65),
+            OperatingConditions { ambient_temp_k: 298.15, ..Default::default() },
+        );
+        let cases = [
+            (load_mof_properties(1005).unwrap(), OperatingConditions::default()),
+            fractional.clone(),
+        ];
+        for (properties, conditions) in cases {
+            for step_k in [1.0, 2.5, 5.0, 10.0] {
+                let mut serial = ThermalFluidSimulator::new(properties.clone(), conditions.clone());
+                let (expected, visited) = serial_optimum(&mut serial, step_k);
+
+                let mut parallel =
+                    ThermalFluidSimulator::new(properties.clone(), conditions.clone());
+                let config = OptimizationConfig { temp_step_k: step_k, ..Default::default() };
+                assert_eq!(parallel.sweep_candidates(&config), visited);
+                let optimized = parallel.optimize_conditions_with_step(step_k).unwrap();
+                assert_eq!(optimized.regeneration_temp_k, expected);
+            }
+        }
+
+        // The 1 K sweep reaches 400.15 K; truncating the bounds stopped at 399 K
+        let mut serial = ThermalFluidSimulator::new(fractional.0, fractional.1);
+        let (_, visited) = serial_optimum(&mut serial, 1.0);
+        assert_eq!(visited.first(), Some(&328.15));
+        assert!((visited.last().unwrap() - 400.15).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_zero_step_rejected() {
+        let mut simulator = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        );
+        let err = simulator.optimize_conditions_with_step(0.0).unwrap_err();
+        assert!(matches!(err, ThermalError::InvalidParameter(_)));
+    }
+
+    #[test]
+    fn test_optimization_config_validation() {
+        assert!(OptimizationConfig::default().validate().is_ok());
+
+        let invalid = [
+            OptimizationConfig { temp_step_k: 0.0, ..Default::default() },
+            OptimizationConfig { safety_margin: 0.0, ..Default::default() },
+            OptimizationConfig { safety_margin: 1.1, ..Default::default() },
+            OptimizationConfig { max_risk: -0.1, ..Default::default() },
+            OptimizationConfig { max_risk: 1.5, ..Default::default() },
+        ];
+        for config in invalid {
+            assert!(matches!(config.validate(), Err(ThermalError::InvalidParameter(_))));
+        }
+    }
+
+    #[test]
+    fn test_optimization_config_bounds_sweep() {
+        let properties = load_mof_properties(1005).unwrap();
+        let conditions = OperatingConditions::default();
+
+        let mut default_run = ThermalFluidSimulator::new(properties.clone(), conditions.clone());
+        let mut configured = ThermalFluidSimulator::new(properties.clone(), conditions.clone());
+        assert_eq!(
+            default_run.optimize_conditions().unwrap().regeneration_temp_k,
+            configured
+                .optimize_conditions_with(&OptimizationConfig::default(), None)
+                .unwrap()
+                .regeneration_temp_k
+        );
+
+        let fine = OptimizationConfig {
+            temp_step_k: 0.5,
+            safety_margin: 0.7,
+            max_risk: 0.3,
+        };
+        let mut tuned = ThermalFluidSimulator::new(properties.clone(), conditions);
+        let optimized = tuned.optimize_conditions_with(&fine, None).unwrap();
+        assert!(optimized.regeneration_temp_k >= 298.0 + 30.0);
+        assert!(optimized.regeneration_temp_k < properties.thermal_stability_k * 0.7);
+    }
+
+    #[test]
+    fn test_risk_score_continuous_and_monotonic() {
+        let properties = MOFThermalProperties::default();
+        let risk_at = |temp: f64| {
+            let conditions = OperatingConditions {
+                regeneration_temp_k: temp,
+                ..Default::default()
+            };
+            ThermalFluidSimulator::new(properties.clone(), conditions)
+                .simulate_temperature_swing()
+                .unwrap()
+                .risk_score
+        };
+
+        let mut previous = 0.0;
+        for temp in (300..573).step_by(5) {
+            let risk = risk_at(temp as f64);
+            assert!(risk > previous, "risk not increasing at {} K", temp);
+            assert!((0.0..=1.0).contains(&risk));
+            previous = risk;
+        }
+
+        // Continuous across the safe boundary (0.8 x 573 K)
+        let boundary = 0.8 * properties.thermal_stability_k;
+        assert!((risk_at(boundary - 1e-6) - risk_at(boundary + 1e-6)).abs() < 1e-6);
+
+        // Saturates at 1.0 at and beyond the stability limit (the bed peaks
+        // fractionally below the setpoint, hence the tolerance)
+        assert!((risk_at(573.0) - 1.0).abs() < 1e-5);
+        assert_eq!(risk_at(650.0), 1.0);
+    }
+
+    #[test]
+    fn test_results_json_round_trip() {
+        let results = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        )
+        .simulate_temperature_swing()
+        .unwrap();
+
+        let path = std::env::temp_dir().join(format!(
+            "mof_thermal_results_{}.json",
+            std::process::id()
+        ));
+        results.to_json_file(&path).unwrap();
+        let restored = SimulationResults::from_json_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(restored, results);
+
+        let json = results.to_json_string().unwrap();
+        for key in [
+            "water_yield_kg",
+            "energy_consumption_kj",
+            "max_temperature_k",
+            "thermal_efficiency",
+            "risk_score",
+        ] {
+            assert!(json.contains(&format!("\"{}\"", key)), "missing key {}", key);
+        }
+        assert!(json.contains('\n'));
+    }
+
+    #[test]
+    fn test_short_cycle_heats_incompletely() {
+        let properties = MOFThermalProperties::default();
+        let simulator =
+            ThermalFluidSimulator::new(properties.clone(), OperatingConditions::default());
+        // 600 kg/m³ × 1000 J/(kg·K) × (0.01 m)² / 0.5 W/(m·K)
+        assert!((simulator.thermal_time_constant_s() - 120.0).abs() < 1e-9);
+
+        let full = simulator.simulate_temperature_swing().unwrap();
+        assert!((full.max_temperature_k - 373.0).abs() < 1e-3);
+
+        let short_conditions = OperatingConditions {
+            cycle_time_seconds: 120.0,
+            ..Default::default()
+        };
+        let short = ThermalFluidSimulator::new(properties.clone(), short_conditions.clone())
+            .simulate_temperature_swing()
+            .unwrap();
+        // 60 s of heating is half a time constant: 1 - e^-0.5 of the 75 K swing
+        let expected = 298.0 + 75.0 * (1.0 - (-0.5f64).exp());
+        assert!((short.max_temperature_k - expected).abs() < 1e-9);
+        assert!(short.energy_consumption_kj < full.energy_consumption_kj);
+
+        // A more conductive MOF heats further in the same short cycle
+        let conductive = MOFThermalProperties {
+            thermal_conductivity: 2.0,
+            ..properties
+        };
+        let faster = ThermalFluidSimulator::new(conductive, short_conditions)
+            .simulate_temperature_swing()
+            .unwrap();
+        assert!(faster.max_temperature_k > short.max_temperature_k);
+    }
+
+    #[test]
+    fn test_batch_preserves_order() {
+        let store = MofPropertyStore::from_csv(fixture("mof_properties.csv")).unwrap();
+        let fips_list = [1005, 1001, 1003, 1005];
+        let conditions = OperatingConditions::default();
+
+        let serial = simulate_batch(&store, &fips_list, &conditions, None);
+        let parallel = simulate_batch_parallel(&store, &fips_list, &conditions);
+        assert_eq!(serial.len(), fips_list.len());
+        assert_eq!(parallel.len(), fips_list.len());
+
+        for ((fips, (serial_fips, serial_result)), (parallel_fips, parallel_result)) in
+            fips_list.iter().zip(&serial).zip(&parallel)
+        {
+            assert_eq!(serial_fips, fips);
+            assert_eq!(parallel_fips, fips);
+            let (serial_result, parallel_result) =
+                (serial_result.as_ref().unwrap(), parallel_result.as_ref().unwrap());
+            assert_results_close(serial_result, parallel_result, 0.0);
+        }
+    }
+
+    #[test]
+    fn test_batch_records_failures_per_entry() {
+        let conditions = OperatingConditions {
+            humidity: 2.0,
+            ..Default::default()
+        };
+        let store = MofPropertyStore::from_csv(fixture("mof_properties.csv")).unwrap();
+        let batch = simulate_batch(&store, &[1001, 1003], &conditions, None);
+
+        assert_eq!(batch.len(), 2);
+        for (_, result) in &batch {
+            assert!(matches!(result, Err(ThermalError::InvalidConditions(_))));
+        }
+
+        let batch = simulate_batch(&store, &[1001, 9999], &OperatingConditions::default(), None);
+        assert!(batch[0].1.is_ok());
+        assert!(matches!(batch[1].1, Err(ThermalError::FipsNotFound(9999))));
+    }
+
+    #[test]
+    fn test_property_store_reads_file_once() {
+        let path = std::env::temp_dir().join(format!(
+            "mof_thermal_store_{}.csv",
+            std::process::id()
+        ));
+        std::fs::copy(fixture("mof_properties.csv"), &path).unwrap();
+        let store = MofPropertyStore::from_csv(&path).unwrap();
+        // Lookups below must not touch the file again
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(store.len(), 3);
+        assert_eq!(store.get(1001).unwrap().density, 580.0);
+        assert_eq!(store.get(1005).unwrap().max_uptake_g_per_g, 0.35);
+        assert_eq!(
+            store.get(1003),
+            Some(&load_mof_properties_from_csv(fixture("mof_properties.csv"), 1003).unwrap())
+        );
+        assert!(store.get(9999).is_none());
+    }
+
+    #[test]
+    fn test_yield_scales_with_bed_mass() {
+        let simulate = |bed_mass_kg: f64| {
+            let conditions = OperatingConditions {
+                bed_mass_kg,
+                ..Default::default()
+            };
+            ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions)
+                .simulate_temperature_swing()
+                .unwrap()
+        };
+
+        let one = simulate(1.0);
+        let ten = simulate(10.0);
+        // Below the ≈0.3 g/g × 5·0.4 / (1 + 5·0.4) ≈ 0.2 kg/kg adsorbed at ambient,
+        // since some water stays on the bed at the regeneration temperature
+        let adsorbed = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        )
+        .equilibrium_loading_g_per_g(298.0);
+        assert!((adsorbed - 0.2).abs() < 0.005);
+        assert!(one.water_yield_kg > 0.15 && one.water_yield_kg < adsorbed);
+        assert!((ten.water_yield_kg - 10.0 * one.water_yield_kg).abs() < 1e-12);
+        assert!(ten.energy_consumption_kj > one.energy_consumption_kj);
+    }
+
+    #[test]
+    fn test_freundlich_vs_langmuir() {
+        let langmuir = IsothermModel::langmuir(5.0);
+        let freundlich = IsothermModel::Freundlich { k: 0.9, n: 2.5 };
+
+        // Same humidity, different isotherms, different uptake and yields;
+        // ambient at the Langmuir reference temperature, so K = 5
+        let conditions = OperatingConditions {
+            ambient_temp_k: 298.15,
+            ..Default::default()
+        };
+        let simulator = ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions);
+        let langmuir_sim = simulator.clone().with_isotherm(langmuir);
+        let freundlich_sim = simulator.with_isotherm(freundlich);
+        assert!((langmuir_sim.estimate_uptake_fraction() - 2.0 / 3.0).abs() < 1e-12);
+        let freundlich_fraction = freundlich_sim.estimate_uptake_fraction();
+        assert!((freundlich_fraction - 0.9 * 0.4f64.powf(0.4)).abs() < 1e-12);
+
+        let yield_of = |sim: &ThermalFluidSimulator| {
+            sim.simulate_temperature_swing().unwrap().water_yield_kg
+        };
+        let langmuir_yield = yield_of(&langmuir_sim);
+        let freundlich_yield = yield_of(&freundlich_sim);
+        assert!((langmuir_yield - freundlich_yield).abs() > 1e-3);
+
+        // Freundlich rises more steeply at low humidity
+        let slope = |model: &IsothermModel| {
+            (model.uptake_fraction(0.002) - model.uptake_fraction(0.001)) / 0.001
+        };
+        assert!(slope(&freundlich) > slope(&langmuir));
+
+        // Clamped to full saturation
+        let strong = IsothermModel::Freundlich { k: 3.0, n: 2.0 };
+        assert_eq!(strong.uptake_fraction(0.9), 1.0);
+    }
+
+    #[test]
+    fn test_invalid_isotherm_rejected() {
+        let simulator = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        )
+        .with_isotherm(IsothermModel::Freundlich { k: 1.0, n: 0.0 });
+
+        let err = simulator.simulate_temperature_swing().unwrap_err();
+        assert!(matches!(err, ThermalError::InvalidParameter(_)));
+    }
+
+    #[test]
+    fn test_load_properties_from_reader() {
+        let data: &[u8] = b"fips,thermal_conductivity,specific_heat,density,\
+thermal_stability_k,heat_of_adsorption,langmuir_k,max_uptake_g_per_g
+1073,0.55,980.0,620.0,600.0,48.0,4.0,0.32
+";
+        let properties = load_mof_properties_from_reader(std::io::Cursor::new(data), 1073).unwrap();
+        assert_eq!(properties.fips, 1073);
+        assert_eq!(properties.thermal_stability_k, 600.0);
+        assert_eq!(properties.max_uptake_g_per_g, 0.32);
+
+        let err = load_mof_properties_from_reader(std::io::Cursor::new(data), 1001).unwrap_err();
+        assert!(matches!(err, ThermalError::FipsNotFound(1001)));
+    }
+
+    #[test]
+    fn test_humidity_sweep() {
+        let simulator = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        );
+        let sweep = simulator.sweep_humidity(0.1, 0.9, 9, None).unwrap();
+
+        assert_eq!(sweep.len(), 9);
+        assert!((sweep[0].0 - 0.1).abs() < 1e-12);
+        assert!((sweep[8].0 - 0.9).abs() < 1e-12);
+        for pair in sweep.windows(2) {
+            assert!(pair[1].1.water_yield_kg > pair[0].1.water_yield_kg);
+        }
+        // Caller's conditions are untouched
+        assert_eq!(simulator.conditions.humidity, 0.4);
+    }
+
+    #[test]
+    fn test_humidity_sweep_validation() {
+        let simulator = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        );
+        for (min, max, steps) in [(-0.1, 0.5, 5), (0.6, 0.5, 5), (0.1, 1.1, 5), (0.1, 0.9, 1)] {
+            let err = simulator.sweep_humidity(min, max, steps, None).unwrap_err();
+            assert!(matches!(err, ThermalError::InvalidParameter(_)));
+        }
+    }
+
+    #[test]
+    fn test_energy_breakdown_sums_to_total() {
+        for temp in [330.0, 373.0, 420.0] {
+            let conditions = OperatingConditions {
+                regeneration_temp_k: temp,
+                ..Default::default()
+            };
+            let results = ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions)
+                .simulate_temperature_swing()
+                .unwrap();
+
+            assert!(results.sensible_energy_kj > 0.0);
+            assert!(results.adsorption_energy_kj > 0.0);
+            let total = results.sensible_energy_kj + results.adsorption_energy_kj
+                - results.recovered_energy_kj;
+            assert!((total - results.energy_consumption_kj).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_coefficient_of_performance() {
+        let cop_at = |temp: f64| {
+            let conditions = OperatingConditions {
+                regeneration_temp_k: temp,
+                ..Default::default()
+            };
+            ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions)
+                .simulate_temperature_swing()
+                .unwrap()
+        };
+
+        // Once desorption is nearly complete, extra temperature only adds heat
+        let mut previous = f64::INFINITY;
+        for temp in [380.0, 400.0, 450.0, 500.0] {
+            let results = cop_at(temp);
+            assert!(results.coefficient_of_performance > 0.0);
+            assert!(results.coefficient_of_performance < previous);
+            let expected =
+                LATENT_HEAT_WATER_KJ_KG * results.water_yield_kg / results.energy_consumption_kj;
+            assert!((results.coefficient_of_performance - expected).abs() < 1e-12);
+            previous = results.coefficient_of_performance;
+        }
+    }
+
+    #[test]
+    fn test_heat_recovery_reduces_net_energy() {
+        let simulate = |heat_recovery_fraction: f64| {
+            let conditions = OperatingConditions {
+                heat_recovery_fraction,
+                ..Default::default()
+            };
+            ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions)
+                .simulate_temperature_swing()
+                .unwrap()
+        };
+
+        // No recovery reproduces the plain heating energy
+        let none = simulate(0.0);
+        assert_eq!(none.recovered_energy_kj, 0.0);
+        assert_eq!(
+            none.energy_consumption_kj,
+            none.sensible_energy_kj + none.adsorption_energy_kj
+        );
+
+        let mut previous = none;
+        for fraction in [0.25, 0.5, 0.75] {
+            let results = simulate(fraction);
+            assert!(results.energy_consumption_kj < previous.energy_consumption_kj);
+            assert!(results.thermal_efficiency > previous.thermal_efficiency);
+            assert_eq!(results.water_yield_kg, previous.water_yield_kg);
+            previous = results;
+        }
+        let gross = previous.sensible_energy_kj + previous.adsorption_energy_kj;
+        assert!((previous.energy_consumption_kj - 0.25 * gross).abs() < 1e-9);
+
+        assert_invalid(
+            OperatingConditions { heat_recovery_fraction: 1.5, ..Default::default() },
+            "heat_recovery_fraction must be within [0, 1]",
+        );
+    }
+
+    #[test]
+    fn test_golden_section_matches_fine_grid() {
+        let properties = MOFThermalProperties::default();
+        let conditions = OperatingConditions {
+            ambient_temp_k: 290.5,
+            ..Default::default()
+        };
+
+        let mut golden = ThermalFluidSimulator::new(properties.clone(), conditions.clone());
+        let golden_temp = golden.optimize_conditions_golden().unwrap().regeneration_temp_k;
+
+        let fine = OptimizationConfig {
+            temp_step_k: 0.25,
+            ..Default::default()
+        };
+        let mut grid = ThermalFluidSimulator::new(properties, conditions);
+        let grid_temp = grid.optimize_conditions_with(&fine, None).unwrap().regeneration_temp_k;
+
+        assert!(
+            (golden_temp - grid_temp).abs() <= 1.0,
+            "golden {} vs grid {}",
+            golden_temp,
+            grid_temp
+        );
+        assert_eq!(golden.conditions.regeneration_temp_k, golden_temp);
+    }
+
+    #[test]
+    fn test_celsius_conversions() {
+        assert!((celsius_to_kelvin(25.0) - 298.15).abs() < 1e-12);
+        assert!((kelvin_to_celsius(373.15) - 100.0).abs() < 1e-12);
+        for celsius in [-40.0, 0.0, 25.0, 99.5] {
+            assert!((kelvin_to_celsius(celsius_to_kelvin(celsius)) - celsius).abs() < 1e-12);
+        }
+
+        let conditions = OperatingConditions::from_celsius(25.0, 100.0, 0.4, 1.0, 3600.0);
+        assert!((conditions.ambient_temp_k - 298.15).abs() < 1e-12);
+        assert!((conditions.regeneration_temp_k - 373.15).abs() < 1e-12);
+        assert!(conditions.validate().is_ok());
+
+        let results = ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions)
+            .simulate_temperature_swing()
+            .unwrap();
+        let peak_c = results.max_temperature_c();
+        assert!((peak_c - kelvin_to_celsius(results.max_temperature_k)).abs() < 1e-12);
+        assert!(peak_c > 99.0 && peak_c <= 100.0);
+    }
+
+    #[test]
+    fn test_saturation_pressure() {
+        // Steam tables: 3.17 kPa at 25 °C, 101.325 kPa at 100 °C
+        assert!((saturation_pressure(298.15) - 3169.0).abs() / 3169.0 < 0.01);
+        let boiling_error = saturation_pressure(373.15) - STANDARD_PRESSURE_PA;
+        assert!(boiling_error.abs() / STANDARD_PRESSURE_PA < 0.01);
+        assert!(saturation_pressure(400.0) > saturation_pressure(373.15));
+    }
+
+    #[test]
+    fn test_uptake_increases_with_pressure() {
+        let uptake_at = |pressure_atm: f64| {
+            // At the isotherm's reference temperature, so K = langmuir_k
+            let conditions = OperatingConditions {
+                ambient_temp_k: 298.15,
+                pressure_atm,
+                humidity: 0.3,
+                ..Default::default()
+            };
+            ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions)
+                .estimate_uptake_fraction()
+        };
+
+        let ambient = uptake_at(1.0);
+        assert!(ambient > 0.0);
+        assert!((ambient - 1.5 / 2.5).abs() < 1e-12);
+        assert!(uptake_at(2.0) > ambient);
+        assert!(uptake_at(0.5) < ambient);
+
+        // Compressed beyond saturation, the isotherm sees p/p_sat = 1
+        assert!((uptake_at(5.0) - 5.0 / 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_safe_simulation_rejects_high_risk() {
+        let properties = MOFThermalProperties::default();
+        let safe = ThermalFluidSimulator::new(properties.clone(), OperatingConditions::default());
+        assert!(safe.simulate_temperature_swing_safe().is_ok());
+
+        let near_limit = OperatingConditions {
+            regeneration_temp_k: properties.thermal_stability_k - 10.0,
+            ..Default::default()
+        };
+        let risky = ThermalFluidSimulator::new(properties, near_limit);
+
+        // Unchecked analysis still returns the result
+        let unchecked = risky.simulate_temperature_swing().unwrap();
+        assert!(unchecked.risk_score > 0.5);
+
+        match risky.simulate_temperature_swing_safe() {
+            Err(ThermalError::UnsafeOperation { risk_score, max_allowed }) => {
+                assert_eq!(risk_score, unchecked.risk_score);
+                assert_eq!(max_allowed, 0.5);
+            }
+            other => panic!("expected UnsafeOperation, got {:?}", other),
+        }
+
+        // A looser threshold admits the same point
+        let tolerant = risky.clone().with_max_allowed_risk(0.95);
+        assert!(tolerant.simulate_temperature_swing_safe().is_ok());
+
+        // A threshold outside [0, 1] would silently disable the guard
+        for invalid in [f64::NAN, 1.5, -0.1] {
+            let unguarded = risky.clone().with_max_allowed_risk(invalid);
+            assert!(matches!(
+                unguarded.simulate_temperature_swing_safe(),
+                Err(ThermalError::InvalidParameter(_))
+            ));
+            assert!(matches!(unguarded.risk_report(), Err(ThermalError::InvalidParameter(_))));
+        }
+    }
+
+    #[test]
+    fn test_stability_margin() {
+        let margin_at = |temp: f64| {
+            let conditions = OperatingConditions {
+                regeneration_temp_k: temp,
+                ..Default::default()
+            };
+            ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions)
+                .simulate_temperature_swing()
+                .unwrap()
+        };
+
+        let safe = margin_at(373.0);
+        assert!(safe.stability_margin_k > 0.0);
+        assert!((safe.stability_margin_k - (573.0 - safe.max_temperature_k)).abs() < 1e-12);
+
+        let mut previous = safe.stability_margin_k;
+        for temp in [400.0, 450.0, 500.0, 550.0] {
+            let margin = margin_at(temp).stability_margin_k;
+            assert!(margin < previous);
+            previous = margin;
+        }
+
+        // Operating above the limit leaves a negative margin
+        assert!(margin_at(600.0).stability_margin_k < 0.0);
+    }
+
+    #[test]
+    fn test_yield_rises_with_regeneration_temperature() {
+        let yield_at = |temp: f64| {
+            let conditions = OperatingConditions {
+                regeneration_temp_k: temp,
+                ..Default::default()
+            };
+            ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions)
+                .simulate_temperature_swing()
+                .unwrap()
+                .water_yield_kg
+        };
+
+        // Hotter regeneration strips more water, approaching the adsorbed loading
+        let adsorbed = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        )
+        .equilibrium_loading_g_per_g(298.0);
+        let mut previous = 0.0;
+        for temp in [310.0, 330.0, 360.0, 400.0, 450.0] {
+            let water = yield_at(temp);
+            assert!(water > previous);
+            assert!(water < adsorbed);
+            previous = water;
+        }
+    }
+
+    #[test]
+    fn test_pareto_front_non_dominated() {
+        let simulator = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        );
+        let config = OptimizationConfig {
+            temp_step_k: 5.0,
+            ..Default::default()
+        };
+        let front = simulator.pareto_front(&config).unwrap();
+
+        assert!(front.len() >= 2, "frontier has {} points", front.len());
+        for a in &front {
+            assert!(a.risk_score < config.max_risk);
+            for b in &front {
+                assert!(!yield_energy_dominates(a, b));
+            }
+        }
+
+        // Every swept point left off the frontier is dominated by one on it
+        for (_, results) in simulator.evaluate_sweep(&config) {
+            if !front.contains(&results) {
+                assert!(front.iter().any(|f| yield_energy_dominates(f, &results)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_legacy_json_gets_defaults() {
+        let legacy = r#"{
+            "water_yield_kg": 0.2,
+            "energy_consumption_kj": 120.0,
+            "max_temperature_k": 373.0,
+            "thermal_efficiency": 0.0016,
+            "risk_score": 0.07
+        }"#;
+        let results: SimulationResults = serde_json::from_str(legacy).unwrap();
+        assert_eq!(results.water_yield_kg, 0.2);
+        assert_eq!(results.schema_version, 0);
+        assert_eq!(results.sensible_energy_kj, 0.0);
+        assert_eq!(results.coefficient_of_performance, 0.0);
+
+        let properties: MOFThermalProperties = serde_json::from_str(
+            r#"{"fips": 1005, "thermal_conductivity": 0.5, "specific_heat": 1000.0,
+                "density": 600.0, "thermal_stability_k": 573.0, "heat_of_adsorption": 45.0}"#,
+        )
+        .unwrap();
+        assert_eq!(properties.langmuir_k, MOFThermalProperties::default().langmuir_k);
+        assert_eq!(properties.max_uptake_g_per_g, 0.3);
+
+        let conditions: OperatingConditions = serde_json::from_str(
+            r#"{"ambient_temp_k": 298.0, "regeneration_temp_k": 373.0, "humidity": 0.4,
+                "pressure_atm": 1.0, "cycle_time_seconds": 3600.0}"#,
+        )
+        .unwrap();
+        assert_eq!(conditions.bed_mass_kg, 1.0);
+        assert_eq!(conditions.heat_recovery_fraction, 0.0);
+        assert!(conditions.validate().is_ok());
+
+        // Freshly simulated results carry the current version
+        let fresh = ThermalFluidSimulator::new(properties, conditions)
+            .simulate_temperature_swing()
+            .unwrap();
+        assert_eq!(fresh.schema_version, CURRENT_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_temperature_profile() {
+        let conditions = OperatingConditions {
+            cycle_time_seconds: 1200.0,
+            ..Default::default()
+        };
+        let simulator = ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions);
+        let profile = simulator.temperature_profile(121);
+
+        assert_eq!(profile.len(), 121);
+        assert_eq!(profile[0], (0.0, 298.0));
+        assert!((profile[120].0 - 1200.0).abs() < 1e-9);
+
+        // Heating half: rising toward, never past, the setpoint
+        let heating: Vec<_> = profile.iter().filter(|(t, _)| *t <= 600.0).collect();
+        for pair in heating.windows(2) {
+            assert!(pair[1].1 > pair[0].1);
+            assert!(pair[1].1 < 373.0);
+        }
+
+        // Peak matches the reported maximum, then the bed cools toward ambient
+        let results = simulator.simulate_temperature_swing().unwrap();
+        let peak = heating.last().unwrap().1;
+        assert!((peak - results.max_temperature_k).abs() < 1e-9);
+        let cooling: Vec<_> = profile.iter().filter(|(t, _)| *t >= 600.0).collect();
+        for pair in cooling.windows(2) {
+            assert!(pair[1].1 < pair[0].1);
+            assert!(pair[1].1 > 298.0);
+        }
+    }
+
+    #[test]
+    fn test_energy_per_liter() {
+        let simulator = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        );
+        let results = simulator.simulate_temperature_swing().unwrap();
+        assert!(results.energy_per_liter_kwh.is_finite());
+        assert!(results.energy_per_liter_kwh > 0.0);
+        let expected = results.energy_consumption_kj / KJ_PER_KWH / results.water_yield_kg;
+        assert!((results.energy_per_liter_kwh - expected).abs() < 1e-12);
+
+        // Dry air: nothing adsorbs, so every liter costs infinite energy
+        let dry = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions {
+                humidity: 0.0,
+                ..Default::default()
+            },
+        )
+        .simulate_temperature_swing()
+        .unwrap();
+        assert_eq!(dry.water_yield_kg, 0.0);
+        assert_eq!(dry.energy_per_liter_kwh, f64::INFINITY);
+
+        // Infinity survives the JSON round trip as null
+        let json = dry.to_json_string().unwrap();
+        assert!(json.contains("\"energy_per_liter_kwh\": null"));
+        let restored: SimulationResults = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, dry);
+    }
+
+    #[test]
+    fn test_optimization_reports_failure() {
+        // Window 328-357 K is well inside the stability limit, but not safe enough.
+        // A mild heat of adsorption keeps the exotherm from dominating the risk
+        let properties = MOFThermalProperties {
+            thermal_stability_k: 420.0,
+            heat_of_adsorption: 20.0,
+            ..Default::default()
+        };
+        let mut simulator = ThermalFluidSimulator::new(properties, OperatingConditions::default());
+        let strict = OptimizationConfig {
+            max_risk: 0.05,
+            ..Default::default()
+        };
+
+        let err = simulator.optimize_conditions_report(&strict).unwrap_err();
+        assert!(matches!(err, ThermalError::OptimizationFailed { evaluated: 3 }));
+        assert!(err.to_string().contains("none of 3 candidates"));
+        assert_eq!(simulator.conditions.regeneration_temp_k, 373.0);
+
+        let report = simulator.optimize_conditions_report(&OptimizationConfig::default()).unwrap();
+        assert_eq!(report.evaluations, 3);
+        assert_eq!(report.conditions.regeneration_temp_k, simulator.conditions.regeneration_temp_k);
+        assert!(report.best.risk_score < 0.5);
+
+        // Ambient too close to the limit: the window is empty
+        let mut hot = ThermalFluidSimulator::new(
+            MOFThermalProperties {
+                thermal_stability_k: 420.0,
+                ..Default::default()
+            },
+            OperatingConditions {
+                ambient_temp_k: 330.0,
+                ..Default::default()
+            },
+        );
+        assert!(matches!(
+            hot.optimize_conditions(),
+            Err(ThermalError::OptimizationFailed { evaluated: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_layered_bed_between_single_layers() {
+        let store = MofPropertyStore::from_csv(fixture("mof_properties.csv")).unwrap();
+        let (low, high) = (store.get(1001).unwrap(), store.get(1005).unwrap());
+        let simulator = ThermalFluidSimulator::new(low.clone(), OperatingConditions::default());
+        let single = |properties: &MOFThermalProperties| {
+            ThermalFluidSimulator::new(properties.clone(), OperatingConditions::default())
+                .simulate_temperature_swing()
+                .unwrap()
+        };
+        let (low_only, high_only) = (single(low), single(high));
+
+        let bed = LayeredBed::uniform(vec![low.clone(), high.clone()]);
+        let combined = simulator.simulate_layered(&bed).unwrap();
+
+        let min_yield = low_only.water_yield_kg.min(high_only.water_yield_kg);
+        let max_yield = low_only.water_yield_kg.max(high_only.water_yield_kg);
+        assert!(min_yield < combined.water_yield_kg && combined.water_yield_kg < max_yield);
+        let midpoint = (low_only.water_yield_kg + high_only.water_yield_kg) / 2.0;
+        assert!((combined.water_yield_kg - midpoint).abs() < 1e-12);
+        assert_eq!(combined.risk_score, low_only.risk_score.max(high_only.risk_score));
+
+        // A single full-mass layer reproduces the plain simulation
+        let solo = simulator.simulate_layered(&LayeredBed::uniform(vec![low.clone()])).unwrap();
+        assert_results_close(&solo, &low_only, 1e-12);
+
+        let lopsided = LayeredBed {
+            layers: vec![low.clone(), high.clone()],
+            mass_fractions: vec![0.5, 0.6],
+        };
+        assert!(matches!(
+            simulator.simulate_layered(&lopsided),
+            Err(ThermalError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_clamp_to_safe() {
+        let properties = MOFThermalProperties::default();
+        let mut conditions = OperatingConditions {
+            regeneration_temp_k: 560.0,
+            ..Default::default()
+        };
+
+        assert!(conditions.clamp_to_safe(&properties, 0.85));
+        assert!((conditions.regeneration_temp_k - 573.0 * 0.85).abs() < 1e-9);
+        // Already within the margin: a second call leaves it alone
+        assert!(!conditions.clamp_to_safe(&properties, 0.85));
+        assert!((conditions.regeneration_temp_k - 573.0 * 0.85).abs() < 1e-9);
+
+        let mut default = OperatingConditions::default();
+        assert!(!default.clamp_to_safe(&properties, 0.85));
+        assert_eq!(default.regeneration_temp_k, 373.0);
+    }
+
+    #[test]
+    fn test_short_cycle_flags_insufficient_cooling() {
+        let simulate = |cycle_time_seconds: f64| {
+            ThermalFluidSimulator::new(
+                MOFThermalProperties::default(),
+                OperatingConditions {
+                    cycle_time_seconds,
+                    ..Default::default()
+                },
+            )
+            .simulate_temperature_swing()
+            .unwrap()
+        };
+
+        // τ = 120 s: cooling 75 K to within 1 K takes about 520 s
+        let hourly = simulate(3600.0);
+        let tau = ThermalFluidSimulator::default().thermal_time_constant_s();
+        let ambient = OperatingConditions::default().ambient_temp_k;
+        let expected = tau * (hourly.max_temperature_k - ambient).ln();
+        assert!((hourly.cooling_time_seconds - expected).abs() < 1e-9);
+        assert!(!hourly.insufficient_cycle_time);
+
+        // A 10-minute cycle leaves only 300 s to cool
+        let short = simulate(600.0);
+        assert!(short.cooling_time_seconds > 300.0);
+        assert!(short.insufficient_cycle_time);
+    }
+
+    #[test]
+    fn test_mass_transfer_limits_fast_cycles() {
+        let simulate = |cycle_time_seconds: f64| {
+            ThermalFluidSimulator::new(
+                MOFThermalProperties::default(),
+                OperatingConditions {
+                    cycle_time_seconds,
+                    ..Default::default()
+                },
+            )
+            .simulate_temperature_swing()
+            .unwrap()
+        };
+
+        // k = 0.01/s: an hour-long cycle saturates, a 1-minute cycle does not
+        let long = simulate(3600.0);
+        assert!(long.uptake_efficiency > 0.999);
+        assert!(!long.mass_transfer_limited);
+
+        let short = simulate(60.0);
+        assert!((short.uptake_efficiency - (1.0 - (-0.3f64).exp())).abs() < 1e-12);
+        assert!(short.mass_transfer_limited);
+
+        // Same peak temperature, so the yield gap is purely kinetic
+        let mut unlimited = ThermalFluidSimulator::new(
+            MOFThermalProperties {
+                mass_transfer_coeff: 1e3,
+                ..Default::default()
+            },
+            OperatingConditions {
+                cycle_time_seconds: 60.0,
+                ..Default::default()
+            },
+        )
+        .simulate_temperature_swing()
+        .unwrap();
+        assert!(!unlimited.mass_transfer_limited);
+        assert!(short.water_yield_kg < unlimited.water_yield_kg);
+        unlimited.water_yield_kg *= short.uptake_efficiency;
+        assert!((short.water_yield_kg - unlimited.water_yield_kg).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_csv_optional_mass_transfer_column() {
+        let data: &[u8] = b"fips,thermal_conductivity,specific_heat,density,\
+thermal_stability_k,heat_of_adsorption,langmuir_k,max_uptake_g_per_g,mass_transfer_coeff
+1001,0.5,1000,600,573,45,5,0.3,0.002
+1003,0.5,1000,600,573,45,5,0.3,
+";
+        let with_value = load_mof_properties_from_reader(std::io::Cursor::new(data), 1001).unwrap();
+        assert_eq!(with_value.mass_transfer_coeff, 0.002);
+        let blank = load_mof_properties_from_reader(std::io::Cursor::new(data), 1003).unwrap();
+        assert_eq!(blank.mass_transfer_coeff, MOFThermalProperties::default().mass_transfer_coeff);
+
+        // Files without the column use the default
+        let properties = load_mof_properties_from_csv(fixture("mof_properties.csv"), 1001).unwrap();
+        assert_eq!(properties.mass_transfer_coeff, 0.01);
+    }
+
+    #[test]
+    fn test_compare_mofs_dominant() {
+        let conditions = OperatingConditions::default();
+        let base = MOFThermalProperties::default();
+        // Same heat capacity per volume (so the same heating curve, water and
+        // risk), but half the specific heat: less energy to heat the bed
+        let better = MOFThermalProperties {
+            specific_heat: 500.0,
+            density: 1200.0,
+            ..base.clone()
+        };
+
+        assert_eq!(compare_mofs(&base, &better, &conditions).unwrap(), Some(&better));
+        assert_eq!(compare_mofs(&better, &base, &conditions).unwrap(), Some(&better));
+
+        let results = ThermalFluidSimulator::new(base.clone(), conditions.clone())
+            .simulate_temperature_swing()
+            .unwrap();
+        assert!(!results.dominates(&results));
+    }
+
+    #[test]
+    fn test_compare_mofs_incomparable() {
+        let conditions = OperatingConditions::default();
+        let stable = MOFThermalProperties::default();
+        // More capacity but a much lower stability limit: a yield-vs-risk trade-off
+        let fragile = MOFThermalProperties {
+            max_uptake_g_per_g: 0.4,
+            thermal_stability_k: 430.0,
+            ..stable.clone()
+        };
+
+        let simulate = |properties: &MOFThermalProperties| {
+            ThermalFluidSimulator::new(properties.clone(), conditions.clone())
+                .simulate_temperature_swing()
+                .unwrap()
+        };
+        let (stable_results, fragile_results) = (simulate(&stable), simulate(&fragile));
+        assert!(fragile_results.water_yield_kg > stable_results.water_yield_kg);
+        assert!(fragile_results.risk_score > stable_results.risk_score);
+
+        assert_eq!(compare_mofs(&stable, &fragile, &conditions).unwrap(), None);
+    }
+
+    #[test]
+    fn test_optimization_logs_selection() {
+        let logger = capture_logs();
+        let mut simulator = ThermalFluidSimulator::new(
+            load_mof_properties(1979).unwrap(),
+            OperatingConditions::default(),
+        );
+        let optimized = simulator.optimize_conditions().unwrap();
+
+        // Other tests log concurrently, so look only for this run's FIPS code
+        let lines = logger.lines.lock().unwrap();
+        let ours: Vec<_> = lines.iter().filter(|line| line.contains("FIPS 1979")).collect();
+        assert!(ours.iter().any(|line| line.starts_with("DEBUG") && line.contains("candidate")));
+        let selected = format!("selected {:.1} K", optimized.regeneration_temp_k);
+        assert!(ours.iter().any(|line| line.contains(&selected)));
+        assert!(ours.iter().any(|line| line.starts_with("TRACE")));
+    }
+
+    #[test]
+    fn test_yield_is_working_capacity() {
+        let conditions = OperatingConditions::default();
+        let simulator = ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions);
+        let results = simulator.simulate_temperature_swing().unwrap();
+
+        // Capacity falls with temperature, so the hot bed releases water
+        let adsorption = simulator.equilibrium_loading_g_per_g(298.0);
+        let desorption = simulator.equilibrium_loading_g_per_g(results.max_temperature_k);
+        assert!(simulator.saturation_capacity_g_per_g(298.0) > 0.29);
+        assert!(simulator.saturation_capacity_g_per_g(373.0) < 0.1);
+        assert!(adsorption > desorption);
+        let expected = (adsorption - desorption) * results.uptake_efficiency;
+        assert!((results.water_yield_kg - expected).abs() < 1e-12);
+        assert!(results.water_yield_kg > 0.0);
+
+        // No swing, no working capacity: equal temperatures are rejected outright
+        let flat = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions {
+                regeneration_temp_k: 298.0,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            flat.equilibrium_loading_g_per_g(298.0) - flat.equilibrium_loading_g_per_g(298.0),
+            0.0
+        );
+        assert!(matches!(
+            flat.simulate_temperature_swing(),
+            Err(ThermalError::InvalidConditions(_))
+        ));
+    }
+
+    #[test]
+    fn test_monte_carlo_reproducible() {
+        let simulator = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        );
+        let sigma = PropertyUncertainty {
+            thermal_conductivity: 0.05,
+            specific_heat: 50.0,
+            thermal_stability_k: 15.0,
+            heat_of_adsorption: 2.0,
+            max_uptake_g_per_g: 0.02,
+            ..Default::default()
+        };
+
+        let first = simulator.monte_carlo(200, 42, &sigma);
+        let second = simulator.monte_carlo(200, 42, &sigma);
+        assert_eq!(first, second);
+        assert_eq!(first.samples, 200);
+
+        let yields = first.water_yield_kg;
+        assert!(yields.std_dev > 0.0);
+        assert!(yields.p5 < yields.p50 && yields.p50 < yields.p95);
+        let nominal = simulator.simulate_temperature_swing().unwrap();
+        assert!((yields.mean - nominal.water_yield_kg).abs() < 3.0 * yields.std_dev);
+
+        assert_ne!(simulator.monte_carlo(200, 7, &sigma), first);
+
+        // No uncertainty collapses every sample onto the nominal result
+        let exact = simulator.monte_carlo(10, 42, &PropertyUncertainty::default());
+        assert!(exact.water_yield_kg.std_dev < 1e-12);
+        assert_eq!(exact.risk_score.p95, nominal.risk_score);
+    }
+
+    #[test]
+    fn test_daily_water_production() {
+        let simulator_for = |cycle_time_seconds: f64| {
+            ThermalFluidSimulator::new(
+                MOFThermalProperties::default(),
+                OperatingConditions {
+                    cycle_time_seconds,
+                    ..Default::default()
+                },
+            )
+        };
+
+        // Hourly cycles cool in time, so 24 fit in a day
+        let hourly = simulator_for(3600.0);
+        let per_cycle = hourly.simulate_temperature_swing().unwrap().water_yield_kg;
+        let daily = hourly.daily_water_production_kg().unwrap();
+        assert!((daily - 24.0 * per_cycle).abs() < 1e-12);
+
+        // Cooling overruns a 10-minute cycle, stretching the period past 600 s
+        let short = simulator_for(600.0);
+        let results = short.simulate_temperature_swing().unwrap();
+        assert!(results.insufficient_cycle_time);
+        let cycles = (86_400.0 / (300.0 + results.cooling_time_seconds)).floor();
+        assert!(cycles < 144.0);
+        let daily = short.daily_water_production_kg().unwrap();
+        assert!((daily - cycles * results.water_yield_kg).abs() < 1e-12);
+
+        // A two-day cycle never completes within a day
+        assert_eq!(simulator_for(172_800.0).daily_water_production_kg().unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_sweep_ambient_skips_points_above_setpoint() {
+        let simulator = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions {
+                regeneration_temp_k: 320.0,
+                ..Default::default()
+            },
+        );
+
+        // 280, 290, ..., 330 K: 320 and 330 K leave no swing below the setpoint
+        let sweep = simulator.sweep_ambient_temp(280.0, 330.0, 6).unwrap();
+        let temps: Vec<f64> = sweep.iter().map(|(t, _)| *t).collect();
+        assert_eq!(temps, vec![280.0, 290.0, 300.0, 310.0]);
+        for (_, results) in &sweep {
+            assert!(results.energy_consumption_kj > 0.0);
+            assert!(results.sensible_energy_kj > 0.0);
+        }
+        // Warmer air narrows the swing, so heating takes less energy
+        assert!(sweep[0].1.sensible_energy_kj > sweep[3].1.sensible_energy_kj);
+        assert_eq!(simulator.conditions.ambient_temp_k, 298.0);
+
+        for (min, max, steps) in [(300.0, 290.0, 5), (0.0, 300.0, 5), (280.0, 300.0, 1)] {
+            assert!(matches!(
+                simulator.sweep_ambient_temp(min, max, steps),
+                Err(ThermalError::InvalidParameter(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_efficiency_modes() {
+        let simulator = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        );
+        assert_eq!(simulator.efficiency_mode(), EfficiencyMode::WaterPerKj);
+        let raw = simulator.simulate_temperature_swing().unwrap();
+        assert_eq!(raw.efficiency_mode, EfficiencyMode::WaterPerKj);
+        assert!(
+            (raw.thermal_efficiency - raw.water_yield_kg / raw.energy_consumption_kj).abs() < 1e-15
+        );
+
+        let with_mode = |mode| {
+            simulator
+                .clone()
+                .with_efficiency_mode(mode)
+                .simulate_temperature_swing()
+                .unwrap()
+        };
+        let dimensionless = with_mode(EfficiencyMode::Dimensionless);
+        assert!(
+            (dimensionless.thermal_efficiency - dimensionless.coefficient_of_performance).abs()
+                < 1e-12
+        );
+
+        let second_law = with_mode(EfficiencyMode::SecondLaw);
+        assert_eq!(second_law.efficiency_mode, EfficiencyMode::SecondLaw);
+        assert!(second_law.thermal_efficiency > 0.0 && second_law.thermal_efficiency < 1.0);
+        // Only the normalization changes
+        assert_eq!(second_law.water_yield_kg, raw.water_yield_kg);
+        assert_eq!(second_law.energy_consumption_kj, raw.energy_consumption_kj);
+    }
+
+    #[test]
+    fn test_simulator_from_config_file() {
+        let from_toml =
+            ThermalFluidSimulator::from_config_file(fixture("simulation_config.toml")).unwrap();
+        let from_json =
+            ThermalFluidSimulator::from_config_file(fixture("simulation_config.json")).unwrap();
+
+        assert_eq!(from_toml.properties, from_json.properties);
+        assert_eq!(from_toml.properties.fips, 1003);
+        assert_eq!(from_toml.conditions.bed_mass_kg, 2.0);
+        assert_eq!(from_toml.conditions.heat_recovery_fraction, 0.0);
+
+        let results = from_toml.simulate_temperature_swing().unwrap();
+        assert!(results.water_yield_kg > 0.0);
+        assert_results_close(&results, &from_json.simulate_temperature_swing().unwrap(), 0.0);
+
+        let err = ThermalFluidSimulator::from_config_file(fixture("mof_properties.csv"))
+            .unwrap_err();
+        assert!(matches!(err, ThermalError::Json(_)));
+
+        let err =
+            ThermalFluidSimulator::from_config_file(fixture("simulation_config_bad_property.toml"))
+                .unwrap_err();
+        assert!(matches!(
+            err,
+            ThermalError::InvalidProperties(ref message) if message.contains("density")
+        ));
+    }
+
+    #[test]
+    fn test_required_bed_mass_round_trip() {
+        let simulator = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        );
+        let bed_mass_kg = simulator.required_bed_mass_kg(100.0).unwrap();
+        assert!(bed_mass_kg > 0.0);
+
+        let sized = simulator.with_conditions(OperatingConditions {
+            bed_mass_kg,
+            ..OperatingConditions::default()
+        });
+        assert!((sized.daily_water_production_kg().unwrap() - 100.0).abs() < 1e-9);
+
+        // Dry air leaves nothing to desorb
+        let dry = simulator.with_conditions(OperatingConditions {
+            humidity: 0.0,
+            ..OperatingConditions::default()
+        });
+        assert!(matches!(
+            dry.required_bed_mass_kg(100.0),
+            Err(ThermalError::InvalidConditions(_))
+        ));
+        assert!(matches!(
+            simulator.required_bed_mass_kg(-1.0),
+            Err(ThermalError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_hysteresis_reduces_yield() {
+        let simulator = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        );
+        assert_eq!(simulator.hysteresis_factor(), 1.0);
+        let reversible = simulator.simulate_temperature_swing().unwrap();
+        let hysteretic = simulator
+            .clone()
+            .with_hysteresis_factor(1.5)
+            .simulate_temperature_swing()
+            .unwrap();
+
+        assert!(hysteretic.water_yield_kg < reversible.water_yield_kg);
+        // Only the residual loading changes: the extra retained water is half of it
+        let residual = simulator.equilibrium_loading_g_per_g(reversible.max_temperature_k)
+            * reversible.uptake_efficiency;
+        let lost = reversible.water_yield_kg - hysteretic.water_yield_kg;
+        assert!((lost - 0.5 * residual).abs() < 1e-12);
+
+        // A factor below 1 would release more water than was adsorbed
+        assert!(matches!(
+            simulator.with_hysteresis_factor(0.5).simulate_temperature_swing(),
+            Err(ThermalError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_degenerate_properties_rejected() {
+        let simulate = |properties: MOFThermalProperties| {
+            ThermalFluidSimulator::new(properties, OperatingConditions::default())
+                .simulate_temperature_swing()
+        };
+
+        let err = simulate(MOFThermalProperties {
+            thermal_stability_k: 0.0,
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert!(matches!(err, ThermalError::InvalidProperties(_)));
+        assert!(err.to_string().contains("thermal_stability_k"));
+
+        let err = simulate(MOFThermalProperties {
+            specific_heat: 0.0,
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert!(matches!(err, ThermalError::InvalidProperties(_)));
+        assert!(err.to_string().contains("specific_heat"));
+
+        for properties in [
+            MOFThermalProperties { thermal_conductivity: f64::NAN, ..Default::default() },
+            MOFThermalProperties { density: f64::INFINITY, ..Default::default() },
+            MOFThermalProperties { max_uptake_g_per_g: -0.1, ..Default::default() },
+            // Passes validation but overflows the capacity exponential
+            MOFThermalProperties { heat_of_adsorption: 1e300, ..Default::default() },
+        ] {
+            assert!(matches!(simulate(properties), Err(ThermalError::InvalidProperties(_))));
+        }
+
+        let infinite_cycle = OperatingConditions {
+            cycle_time_seconds: f64::INFINITY,
+            ..Default::default()
+        };
+        assert!(matches!(infinite_cycle.validate(), Err(ThermalError::InvalidConditions(_))));
+    }
+
+    #[test]
+    fn test_pressure_swing_vs_temperature_swing() {
+        let simulator = ThermalFluidSimulator::new(
+            load_mof_properties(1005).unwrap(),
+            OperatingConditions::default(),
+        );
+        let tsa = simulator.simulate_temperature_swing().unwrap();
+        let psa = simulator.simulate_pressure_swing(0.1).unwrap();
+
+        // A modest vacuum recovers less water than heating to 373 K, at lower risk
+        assert!(psa.water_yield_kg > 0.0);
+        assert!(psa.water_yield_kg < tsa.water_yield_kg);
+        assert!(psa.risk_score < tsa.risk_score);
+        assert_eq!(psa.max_temperature_k, simulator.conditions.ambient_temp_k);
+        assert!(psa.energy_per_liter_kwh.is_finite());
+
+        // A deeper vacuum releases more water but costs more work per kg
+        let deep = simulator.simulate_pressure_swing(0.01).unwrap();
+        assert!(deep.water_yield_kg > psa.water_yield_kg);
+        assert!(deep.energy_per_liter_kwh > psa.energy_per_liter_kwh);
+
+        for low in [0.0, 1.0, 2.0, f64::NAN] {
+            assert!(matches!(
+                simulator.simulate_pressure_swing(low),
+                Err(ThermalError::InvalidParameter(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_cycle_time_optimum_is_interior() {
+        let mut simulator = ThermalFluidSimulator::new(
+            load_mof_properties(1005).unwrap(),
+            OperatingConditions::default(),
+        );
+        let (min_s, max_s, steps) = (60.0, 20_000.0, 100);
+        let (best_s, daily_kg) = simulator.optimize_cycle_time(min_s, max_s, steps).unwrap();
+
+        assert!(best_s > min_s && best_s < max_s);
+        assert!(daily_kg.is_finite() && daily_kg > 0.0);
+        assert_eq!(simulator.conditions.cycle_time_seconds, best_s);
+        assert_eq!(simulator.daily_water_production_kg().unwrap(), daily_kg);
+        for endpoint in [min_s, max_s] {
+            let mut at_endpoint = simulator.clone();
+            at_endpoint.conditions.cycle_time_seconds = endpoint;
+            assert!(at_endpoint.daily_water_production_kg().unwrap() < daily_kg);
+        }
+
+        assert!(matches!(
+            simulator.optimize_cycle_time(100.0, 50.0, 10),
+            Err(ThermalError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            simulator.optimize_cycle_time(60.0, 600.0, 1),
+            Err(ThermalError::InvalidParameter(_))
+        ));
+
+        // Failed candidates are skipped; only an empty sweep is an error
+        let mut overheated = ThermalFluidSimulator::new(
+            load_mof_properties(1005).unwrap(),
+            OperatingConditions {
+                regeneration_temp_k: load_mof_properties(1005).unwrap().thermal_stability_k,
+                ..OperatingConditions::default()
+            },
+        )
+        .with_stability_policy(StabilityPolicy::Error);
+        assert!(matches!(
+            overheated.optimize_cycle_time(min_s, max_s, 10),
+            Err(ThermalError::OptimizationFailed { evaluated: 10 })
+        ));
+    }
+
+    #[test]
+    fn test_absolute_humidity_conversion() {
+        // Saturated air at 25 °C holds about 23 g/m³
+        let saturated = relative_to_absolute_humidity(1.0, 298.15, 1.0);
+        assert!((saturated - 23.0).abs() < 0.5, "{}", saturated);
+
+        let mut previous = 0.0;
+        for temp_k in [278.15, 288.15, 298.15, 308.15, 318.15] {
+            let absolute = relative_to_absolute_humidity(0.5, temp_k, 1.0);
+            assert!(absolute > previous);
+            previous = absolute;
+
+            let rh = absolute_to_relative_humidity(absolute, temp_k, 1.0);
+            assert!((rh - 0.5).abs() < 1e-12);
+        }
+
+        // The same vapor load is relatively drier in warmer air
+        let absolute = relative_to_absolute_humidity(0.6, 293.15, 1.0);
+        assert!(absolute_to_relative_humidity(absolute, 303.15, 1.0) < 0.6);
+    }
+
+    #[test]
+    fn test_adsorption_energy_scales_with_desorbed_water() {
+        let properties = MOFThermalProperties::default();
+        let simulate = |bed_mass_kg: f64| {
+            let conditions = OperatingConditions {
+                bed_mass_kg,
+                ..Default::default()
+            };
+            ThermalFluidSimulator::new(properties.clone(), conditions)
+                .simulate_temperature_swing()
+                .unwrap()
+        };
+        let (single, double) = (simulate(1.0), simulate(2.0));
+
+        // 45 kJ/mol / 18.015 g/mol ≈ 2498 kJ per kg of water desorbed
+        let per_kg = properties.heat_of_adsorption / WATER_MOLAR_MASS_G * G_PER_KG;
+        for results in [&single, &double] {
+            let expected = per_kg * results.water_yield_kg;
+            assert!((results.adsorption_energy_kj - expected).abs() < 1e-9 * expected);
+        }
+        let ratio = double.adsorption_energy_kj / single.adsorption_energy_kj;
+        assert!((ratio - 2.0).abs() < 1e-12);
+
+        // No working capacity, no heat of adsorption to supply
+        let dry = ThermalFluidSimulator::new(
+            properties.clone(),
+            OperatingConditions {
+                humidity: 0.0,
+                ..Default::default()
+            },
+        )
+        .simulate_temperature_swing()
+        .unwrap();
+        assert_eq!(dry.adsorption_energy_kj, 0.0);
+    }
+
+    #[test]
+    fn test_joint_optimum_at_least_temperature_only() {
+        let properties = load_mof_properties(1005).unwrap();
+        let conditions = OperatingConditions::default();
+        let mut temp_only = ThermalFluidSimulator::new(properties.clone(), conditions.clone());
+        let report = temp_only
+            .optimize_conditions_report(&OptimizationConfig::default())
+            .unwrap();
+
+        // The grid covers the default sweep (328-478 K in 10 K steps) and the
+        // fixed humidity as its lower endpoint
+        let mut joint = ThermalFluidSimulator::new(properties, conditions.clone());
+        let (chosen, results) = joint
+            .optimize_joint((328.0, 478.0), (conditions.humidity, 0.7), 16)
+            .unwrap();
+
+        assert!(results.thermal_efficiency >= report.best.thermal_efficiency);
+        assert!(results.risk_score < 0.5);
+        assert_eq!(joint.conditions.regeneration_temp_k, chosen.regeneration_temp_k);
+        assert_eq!(joint.conditions.humidity, chosen.humidity);
+        assert!((conditions.humidity..=0.7).contains(&chosen.humidity));
+
+        assert!(matches!(
+            joint.optimize_joint((400.0, 350.0), (0.2, 0.6), 5),
+            Err(ThermalError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            joint.optimize_joint((350.0, 400.0), (0.2, 1.5), 5),
+            Err(ThermalError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_results_csv_round_trip() {
+        let store = MofPropertyStore::from_csv(fixture("mof_properties.csv")).unwrap();
+        let mut rows: Vec<(u32, SimulationResults)> = simulate_batch(
+            &store,
+            &[1001, 1003],
+            &OperatingConditions::default(),
+            None,
+        )
+        .into_iter()
+        .map(|(fips, results)| (fips, results.unwrap()))
+        .collect();
+        // A dry run exercises the infinite energy per liter
+        rows.push((
+            1005,
+            ThermalFluidSimulator::new(
+                store.get(1005).unwrap().clone(),
+                OperatingConditions {
+                    humidity: 0.0,
+                    ..Default::default()
+                },
+            )
+            .simulate_temperature_swing()
+            .unwrap(),
+        ));
+        assert!(rows[2].1.energy_per_liter_kwh.is_infinite());
+
+        let path = std::env::temp_dir().join(format!(
+            "mof_thermal_results_{}.csv",
+            std::process::id()
+        ));
+        write_results_csv(&path, &rows).unwrap();
+        let mut reader = csv::Reader::from_path(&path).unwrap();
+        let headers = reader.headers().unwrap().clone();
+        assert_eq!(headers.iter().collect::<Vec<_>>().join(","), SimulationResults::csv_header());
+
+        let result_headers: csv::StringRecord = headers.iter().skip(1).collect();
+        let restored: Vec<(u32, SimulationResults)> = reader
+            .records()
+            .map(|record| {
+                let record = record.unwrap();
+                let fips = record[0].parse().unwrap();
+                let fields: csv::StringRecord = record.iter().skip(1).collect();
+                (fips, fields.deserialize(Some(&result_headers)).unwrap())
+            })
+            .collect();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(restored, rows);
+    }
+
+    #[test]
+    fn test_risk_report_thresholds() {
+        let report_at = |regeneration_temp_k: f64| {
+            let conditions = OperatingConditions {
+                regeneration_temp_k,
+                ..Default::default()
+            };
+            ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions)
+                .risk_report()
+                .unwrap()
+        };
+
+        // 373 K against a 573 K limit
+        let safe = report_at(373.0);
+        assert_eq!(safe.threshold_crossed, None);
+        assert!((safe.temp_ratio - 373.0 / 573.0).abs() < 1e-6);
+        assert!((safe.stability_margin_k - 200.0).abs() < 1e-3);
+        assert!(safe.assessment.starts_with("safe"));
+        let results = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        )
+        .simulate_temperature_swing()
+        .unwrap();
+        assert_eq!(safe.risk_score, results.risk_score);
+
+        assert_eq!(report_at(480.0).threshold_crossed, Some(RiskThreshold::SafeTempRatio));
+        let unsafe_report = report_at(550.0);
+        assert_eq!(unsafe_report.threshold_crossed, Some(RiskThreshold::MaxAllowedRisk));
+        assert!(unsafe_report.assessment.starts_with("unsafe"));
+        assert_eq!(report_at(580.0).threshold_crossed, Some(RiskThreshold::StabilityLimit));
+        assert!(report_at(580.0).stability_margin_k < 0.0);
+    }
+
+    #[test]
+    fn test_working_fluid_changes_energy() {
+        let simulator = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        );
+        assert_eq!(simulator.working_fluid(), WorkingFluid::water());
+        let water = simulator.simulate_temperature_swing().unwrap();
+        let methanol = simulator
+            .clone()
+            .with_working_fluid(WorkingFluid::methanol())
+            .simulate_temperature_swing()
+            .unwrap();
+
+        // Same loadings, but heavier molecules carry less heat of adsorption
+        // per kg and methanol's lower latent heat earns less COP credit
+        assert_eq!(methanol.water_yield_kg, water.water_yield_kg);
+        assert_eq!(methanol.sensible_energy_kj, water.sensible_energy_kj);
+        assert!(methanol.energy_consumption_kj < water.energy_consumption_kj);
+        let ratio = methanol.adsorption_energy_kj / water.adsorption_energy_kj;
+        assert!((ratio - WATER_MOLAR_MASS_G / 32.04).abs() < 1e-12);
+        let cop_per_kj = |r: &SimulationResults| {
+            r.coefficient_of_performance * r.energy_consumption_kj / r.water_yield_kg
+        };
+        assert!((cop_per_kj(&water) - LATENT_HEAT_WATER_KJ_KG).abs() < 1e-9);
+        assert!((cop_per_kj(&methanol) - 1100.0).abs() < 1e-9);
+
+        let invalid = WorkingFluid {
+            molar_mass_g_per_mol: 0.0,
+            ..WorkingFluid::water()
+        };
+        assert!(matches!(
+            simulator.with_working_fluid(invalid).simulate_temperature_swing(),
+            Err(ThermalError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_interpolated_properties_midpoint() {
+        let store = MofPropertyStore::from_csv(fixture("mof_properties.csv")).unwrap();
+
+        // 1002 is missing; a nonexistent neighbor is ignored
+        let midpoint = load_mof_properties_interpolated(&store, 1002, &[1001, 1003, 9999]).unwrap();
+        assert_eq!(midpoint.fips, 1002);
+        let close = |value: f64, expected: f64| (value - expected).abs() < 1e-12 * expected;
+        assert!(close(midpoint.thermal_conductivity, (0.45 + 0.52) / 2.0));
+        assert!(close(midpoint.specific_heat, 985.0));
+        assert!(close(midpoint.density, 595.0));
+        assert!(close(midpoint.thermal_stability_k, 583.0));
+        assert!(close(midpoint.heat_of_adsorption, 45.75));
+        assert!(close(midpoint.langmuir_k, 5.0));
+        assert!(close(midpoint.max_uptake_g_per_g, 0.24));
+        assert!(close(midpoint.mass_transfer_coeff, 0.01));
+
+        // Exact matches are not interpolated
+        let exact = load_mof_properties_interpolated(&store, 1005, &[1001, 1003]).unwrap();
+        assert_eq!(&exact, store.get(1005).unwrap());
+
+        assert!(matches!(
+            load_mof_properties_interpolated(&store, 1002, &[9998, 9999]),
+            Err(ThermalError::FipsNotFound(1002))
+        ));
+        assert!(matches!(
+            load_mof_properties_interpolated(&store, 1002, &[]),
+            Err(ThermalError::FipsNotFound(1002))
+        ));
+    }
+
+    #[test]
+    fn test_progress_callbacks_report_completion() {
+        use std::sync::Mutex;
+
+        // Record every (completed, total) pair; the optimizer calls from workers
+        let check = |run: &dyn Fn(Progress<'_>)| {
+            let calls = Mutex::new(Vec::new());
+            run(&|completed, total| calls.lock().unwrap().push((completed, total)));
+            let mut calls = calls.into_inner().unwrap();
+            calls.sort_unstable();
+            let total = calls[0].1;
+            assert_eq!(calls.len(), total);
+            assert!(calls.iter().enumerate().all(|(i, &call)| call == (i + 1, total)));
+            assert_eq!(calls.last(), Some(&(total, total)));
+            total
+        };
+
+        let store = MofPropertyStore::from_csv(fixture("mof_properties.csv")).unwrap();
+        let conditions = OperatingConditions::default();
+        let batch_total = check(&|progress| {
+            simulate_batch(&store, &[1001, 1003, 9999], &conditions, Some(progress));
+        });
+        assert_eq!(batch_total, 3);
+
+        let simulator = ThermalFluidSimulator::new(store.get(1001).unwrap().clone(), conditions);
+        let sweep_total = check(&|progress| {
+            simulator.sweep_humidity(0.1, 0.9, 5, Some(progress)).unwrap();
+        });
+        assert_eq!(sweep_total, 5);
+
+        let config = OptimizationConfig::default();
+        let optimize_total = check(&|progress| {
+            simulator
+                .clone()
+                .optimize_conditions_with(&config, Some(progress))
+                .unwrap();
+        });
+        assert_eq!(optimize_total, simulator.sweep_candidates(&config).len());
+    }
+
+    #[test]
+    fn test_isotherm_curve_monotonic() {
+        let pressures: Vec<f64> = (0..=20).map(|i| i as f64 / 20.0).collect();
+        for isotherm in [
+            IsothermModel::langmuir(5.0),
+            IsothermModel::Freundlich { k: 1.0, n: 2.0 },
+        ] {
+            let simulator = ThermalFluidSimulator::new(
+                MOFThermalProperties::default(),
+                OperatingConditions::default(),
+            )
+            .with_isotherm(isotherm);
+            let curve = simulator.isotherm_curve(&pressures);
+
+            assert_eq!(curve.len(), pressures.len());
+            assert_eq!(curve[0], (0.0, 0.0));
+            assert!(curve.windows(2).all(|pair| pair[1].1 > pair[0].1));
+            assert!(curve.iter().zip(&pressures).all(|(&(p, _), &expected)| p == expected));
+            assert!(curve.last().unwrap().1 <= simulator.saturation_capacity_g_per_g(298.0));
+
+            // At the feed's own relative pressure the curve gives the simulated loading
+            let feed = simulator.relative_pressure(298.0);
+            let (_, q_feed) = simulator.isotherm_curve(&[feed])[0];
+            assert!((q_feed - simulator.equilibrium_loading_g_per_g(298.0)).abs() < 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_heat_exchanger_recovery() {
+        let simulator = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        );
+        let with_exchanger = |effectiveness: f64| {
+            simulator
+                .clone()
+                .with_heat_exchanger(HeatExchanger::new(effectiveness).unwrap())
+                .simulate_temperature_swing()
+                .unwrap()
+        };
+        let baseline = simulator.simulate_temperature_swing().unwrap();
+
+        assert_eq!(with_exchanger(0.0), baseline);
+        let (half, high) = (with_exchanger(0.5), with_exchanger(0.9));
+        assert!(half.energy_consumption_kj < baseline.energy_consumption_kj);
+        assert!(high.energy_consumption_kj < half.energy_consumption_kj);
+        assert!(high.recovered_energy_kj > half.recovered_energy_kj);
+        // ε = 0.5 recovers half the sensible heat given up cooling to ambient
+        assert!((half.recovered_energy_kj - 0.5 * baseline.sensible_energy_kj).abs() < 1e-9);
+        assert!(high.thermal_efficiency > baseline.thermal_efficiency);
+        assert_eq!(high.water_yield_kg, baseline.water_yield_kg);
+
+        for effectiveness in [-0.1, 1.1, f64::NAN] {
+            assert!(matches!(
+                HeatExchanger::new(effectiveness),
+                Err(ThermalError::InvalidParameter(_))
+            ));
+        }
+        let exchanger = HeatExchanger::new(0.8).unwrap();
+        assert_eq!(exchanger.recovered_energy_kj(300.0, 350.0, 2.0), 0.0);
+        assert!((exchanger.recovered_energy_kj(350.0, 300.0, 2.0) - 80.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_csv_truncated_and_oversized_input() {
+        let header = "fips,thermal_conductivity,specific_heat,density,\
+thermal_stability_k,heat_of_adsorption,langmuir_k,max_uptake_g_per_g\n";
+        let load = |body: &[u8]| {
+            let data = [header.as_bytes(), body].concat();
+            load_mof_properties_from_reader(std::io::Cursor::new(data), 1001)
+        };
+        let csv_error = |result: Result<MOFThermalProperties, ThermalError>| match result {
+            Err(ThermalError::CsvParse(message)) => message,
+            other => panic!("expected CsvParse, got {:?}", other),
+        };
+
+        // Input cut off mid-row
+        let message = csv_error(load(b"1001,0.45,950.0,58"));
+        assert!(message.contains("line 2: missing value for 'thermal_stability_k'"));
+
+        // Overflowing, infinite and NaN literals
+        let huge = format!("1001,0.45,1{},580,573,44,4.5,0.21", "0".repeat(400));
+        let message = csv_error(load(huge.as_bytes()));
+        assert!(message.contains("non-finite number") && message.contains("'specific_heat'"));
+        for value in ["1e400", "inf", "NaN"] {
+            let row = format!("1001,0.45,950,580,573,44,4.5,{}", value);
+            assert!(csv_error(load(row.as_bytes())).contains("'max_uptake_g_per_g'"));
+        }
+
+        // Garbage never panics
+        for body in [&b"\xff\xfe,\x00"[..], b"1001,,,,,,,", b"99999999999,1,1,1,1,1,1,1", b""] {
+            assert!(load(body).is_err());
+        }
+        assert!(matches!(
+            load_mof_properties_from_reader(std::io::Cursor::new(b""), 1001),
+            Err(ThermalError::CsvParse(_))
+        ));
+    }
+
+    #[test]
+    fn test_latent_fraction_falls_with_temperature_swing() {
+        let fractions: Vec<f64> = [340.0, 360.0, 380.0, 420.0, 460.0]
+            .iter()
+            .map(|&regeneration_temp_k| {
+                let conditions = OperatingConditions {
+                    regeneration_temp_k,
+                    ..Default::default()
+                };
+                let results = ThermalFluidSimulator::new(Default::default(), conditions)
+                    .simulate_temperature_swing()
+                    .unwrap();
+                let latent = LATENT_HEAT_WATER_KJ_KG * results.water_yield_kg
+                    / results.energy_consumption_kj;
+                assert!((results.latent_fraction() - latent.clamp(0.0, 1.0)).abs() < 1e-12);
+                results.latent_fraction()
+            })
+            .collect();
+
+        assert!(fractions.iter().all(|f| (0.0..=1.0).contains(f)));
+        assert!(fractions.windows(2).all(|pair| pair[1] < pair[0]));
+    }
+
+    #[test]
+    fn test_altitude_lowers_pressure_and_uptake() {
+        assert_eq!(barometric_pressure_atm(0.0), 1.0);
+        let at_2000 = barometric_pressure_atm(2000.0);
+        assert!((at_2000 - 0.784).abs() < 0.005, "{}", at_2000);
+
+        let simulate = |altitude_m: f64| {
+            let conditions = OperatingConditions::builder().altitude_m(altitude_m).build().unwrap();
+            let simulator =
+                ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions.clone());
+            (conditions, simulator.clone(), simulator.simulate_temperature_swing().unwrap())
+        };
+        let (sea_conditions, sea, sea_results) = simulate(0.0);
+        let (high_conditions, high, high_results) = simulate(2000.0);
+
+        assert_eq!(sea_conditions.absolute_pressure_atm(), 1.0);
+        assert!((high_conditions.absolute_pressure_atm() - at_2000).abs() < 1e-12);
+        // Thinner air carries less vapor into the bed
+        assert!(high.relative_pressure(298.0) < sea.relative_pressure(298.0));
+        let ratio = high.relative_pressure(298.0) / sea.relative_pressure(298.0);
+        assert!((ratio - at_2000).abs() < 1e-12);
+        assert!(high.estimate_uptake_fraction() < sea.estimate_uptake_fraction());
+        assert!(high_results.water_yield_kg < sea_results.water_yield_kg);
+
+        for altitude_m in [-1000.0, 20_000.0, f64::NAN] {
+            let conditions = OperatingConditions {
+                altitude_m,
+                ..Default::default()
+            };
+            assert!(matches!(conditions.validate(), Err(ThermalError::InvalidConditions(_))));
+        }
+    }
+
+    #[test]
+    fn test_pareto_front_sorted_and_reproducible() {
+        let simulator = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        );
+        let config = OptimizationConfig {
+            temp_step_k: 2.0,
+            ..Default::default()
+        };
+        let front = simulator.pareto_front(&config).unwrap();
+
+        assert!(front.windows(2).all(|pair| {
+            let (a, b) = (&pair[0], &pair[1]);
+            a.energy_consumption_kj < b.energy_consumption_kj
+                || (a.energy_consumption_kj == b.energy_consumption_kj
+                    && a.water_yield_kg <= b.water_yield_kg)
+        }));
+        for _ in 0..3 {
+            assert_eq!(simulator.pareto_front(&config).unwrap(), front);
+        }
+    }
+
+    #[test]
+    fn test_temperature_for_desorption_fraction() {
+        let simulator = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        );
+        let temp_k = simulator.temperature_for_desorption_fraction(0.8).unwrap();
+        assert!(temp_k > 298.0 && temp_k < 573.0 * 0.85, "{}", temp_k);
+
+        // Forward model at that setpoint: yield over the adsorbed loading
+        let at_setpoint = simulator.with_conditions(OperatingConditions {
+            regeneration_temp_k: temp_k,
+            ..Default::default()
+        });
+        let results = at_setpoint.simulate_temperature_swing().unwrap();
+        let adsorbed = at_setpoint.equilibrium_loading_g_per_g(298.0)
+            * results.uptake_efficiency
+            * at_setpoint.conditions.bed_mass_kg;
+        assert!((results.water_yield_kg / adsorbed - 0.8).abs() < 0.01);
+
+        let lower = simulator.temperature_for_desorption_fraction(0.5).unwrap();
+        assert!(lower < temp_k);
+        for fraction in [0.0, 1.0, -0.2, f64::NAN] {
+            assert!(matches!(
+                simulator.temperature_for_desorption_fraction(fraction),
+                Err(ThermalError::InvalidParameter(_))
+            ));
+        }
+        // Beyond what the safe limit can regenerate
+        let fragile = ThermalFluidSimulator::new(
+            MOFThermalProperties {
+                thermal_stability_k: 380.0,
+                ..Default::default()
+            },
+            OperatingConditions::default(),
+        );
+        let err = fragile.temperature_for_desorption_fraction(0.99).unwrap_err();
+        assert!(err.to_string().contains("safe limit"));
+    }
+
+    #[test]
+    fn test_biot_number_flags_lumped_model() {
+        let insulating = MOFThermalProperties {
+            thermal_conductivity: 0.1,
+            ..Default::default()
+        };
+        let simulator = ThermalFluidSimulator::new(insulating, OperatingConditions::default());
+        // Bi = h·L / k = 50 × 0.01 / 0.1
+        assert!((simulator.biot_number(0.01, 50.0) - 5.0).abs() < 1e-12);
+
+        let baseline = simulator.simulate_temperature_swing().unwrap();
+        assert!(!baseline.lumped_model_invalid);
+
+        let high_biot = simulator.clone().with_heat_transfer_coeff(50.0);
+        assert_eq!(high_biot.heat_transfer_coeff(), Some(50.0));
+        let results = high_biot.simulate_temperature_swing().unwrap();
+        assert!(results.lumped_model_invalid);
+        assert_eq!(results.water_yield_kg, baseline.water_yield_kg);
+
+        let low_biot = simulator.clone().with_heat_transfer_coeff(0.5);
+        assert!(!low_biot.simulate_temperature_swing().unwrap().lumped_model_invalid);
+
+        assert!(matches!(
+            simulator.with_heat_transfer_coeff(-1.0).simulate_temperature_swing(),
+            Err(ThermalError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_aggregate_region() {
+        let mut store = MofPropertyStore::new();
+        for (fips, stability) in [(1001, 600.0), (1003, 440.0), (1005, 520.0)] {
+            store.insert(MOFThermalProperties {
+                fips,
+                thermal_stability_k: stability,
+                ..Default::default()
+            });
+        }
+        let conditions = OperatingConditions {
+            regeneration_temp_k: 420.0,
+            ..Default::default()
+        };
+        let fips_list = [1001, 1003, 9999, 1005];
+        let summary = aggregate_region(&store, &fips_list, &conditions).unwrap();
+
+        let batch: Vec<_> = simulate_batch(&store, &[1001, 1003, 1005], &conditions, None)
+            .into_iter()
+            .map(|(_, results)| results.unwrap())
+            .collect();
+        let yield_sum: f64 = batch.iter().map(|r| r.water_yield_kg).sum();
+        let energy_sum: f64 = batch.iter().map(|r| r.energy_consumption_kj).sum();
+        let mean_risk = batch.iter().map(|r| r.risk_score).sum::<f64>() / 3.0;
+
+        assert_eq!(summary.county_count, 3);
+        assert!((summary.water_yield_kg - yield_sum).abs() < 1e-12);
+        assert!((summary.energy_consumption_kj - energy_sum).abs() < 1e-9);
+        assert!((summary.mean_risk_score - mean_risk).abs() < 1e-12);
+        assert_eq!(summary.worst_risk_fips, Some(1003));
+        assert_eq!(summary.worst_risk_score, batch[1].risk_score);
+        assert!(batch[1].risk_score > batch[0].risk_score.max(batch[2].risk_score));
+        assert_eq!(summary.missing, vec![9999]);
+
+        let empty = aggregate_region(&store, &[9999], &conditions).unwrap();
+        assert_eq!((empty.county_count, empty.worst_risk_fips), (0, None));
+    }
+
+    #[test]
+    fn test_langmuir_van_t_hoff() {
+        let isotherm = IsothermModel::Langmuir { k: 5.0, reference_temp_k: 300.0 };
+        let heat = MOFThermalProperties::default().heat_of_adsorption;
+        let k_at = |temp_k: f64| isotherm.langmuir_k_at(temp_k, heat).unwrap();
+
+        assert!((k_at(300.0) - 5.0).abs() < 1e-12);
+        assert!(k_at(280.0) > 5.0);
+        assert!(k_at(350.0) < 5.0 && k_at(400.0) < k_at(350.0));
+        assert!((isotherm.uptake_fraction_at(0.4, 300.0, heat) - 2.0 / 3.0).abs() < 1e-12);
+        assert!(isotherm.uptake_fraction_at(0.4, 350.0, heat) < 2.0 / 3.0);
+        assert_eq!(isotherm.langmuir_k_at(350.0, 0.0), Some(5.0));
+        assert_eq!(IsothermModel::Freundlich { k: 1.0, n: 2.0 }.langmuir_k_at(350.0, heat), None);
+
+        let legacy: IsothermModel = serde_json::from_str(r#"{"Langmuir":{"k":5.0}}"#).unwrap();
+        assert_eq!(legacy, IsothermModel::langmuir(5.0));
+        assert!(matches!(
+            IsothermModel::Langmuir { k: 5.0, reference_temp_k: 0.0 }.validate(),
+            Err(ThermalError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_adsorption_exotherm_raises_risk() {
+        let conditions = OperatingConditions::default();
+        let baseline =
+            ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions.clone());
+        let exothermic = ThermalFluidSimulator::new(
+            MOFThermalProperties {
+                heat_of_adsorption: 90.0,
+                specific_heat: 400.0,
+                ..Default::default()
+            },
+            conditions,
+        );
+
+        let rise = exothermic.adsorption_temperature_rise();
+        assert!(rise > 2.0 * baseline.adsorption_temperature_rise());
+        // Water-equivalent check: q·ΔH/M over the loaded bed's heat capacity
+        let captured =
+            exothermic.equilibrium_loading_g_per_g(298.0) * exothermic.uptake_efficiency();
+        let expected = captured * 90.0 / WATER_MOLAR_MASS_G * G_PER_KG * J_PER_KJ
+            / (400.0 + captured * 4186.0);
+        assert!((rise - expected).abs() < 1e-9);
+
+        let baseline_results = baseline.simulate_temperature_swing().unwrap();
+        let exothermic_results = exothermic.simulate_temperature_swing().unwrap();
+        // Both regenerate at a mild 373 K, but the exotherm spikes the bed
+        assert!(exothermic_results.max_temperature_k < 0.8 * 573.0);
+        assert!(exothermic_results.risk_score > SAFE_RISK_CEILING);
+        assert!(exothermic_results.risk_score > 2.0 * baseline_results.risk_score);
+    }
+
+    #[test]
+    fn test_properties_with_overrides() {
+        let properties = MOFThermalProperties::default().with_thermal_stability_k(600.0);
+        assert_eq!(properties.thermal_stability_k, 600.0);
+        assert_eq!(
+            MOFThermalProperties { thermal_stability_k: 573.0, ..properties.clone() },
+            MOFThermalProperties::default()
+        );
+
+        let properties = properties.with_fips(1001).with_heat_of_adsorption(50.0);
+        assert_eq!((properties.fips, properties.heat_of_adsorption), (1001, 50.0));
+        assert_eq!(properties.specific_heat, MOFThermalProperties::default().specific_heat);
+    }
+
+    #[test]
+    fn test_erv_preconditions_inlet_air() {
+        let humid = OperatingConditions {
+            ambient_temp_k: 308.0,
+            humidity: 0.8,
+            ..Default::default()
+        };
+        let erv = ErvStage::new(0.7, 0.6).unwrap();
+        let treated = erv.precondition(&humid, 297.0, 0.5);
+
+        assert!((treated.ambient_temp_k - (308.0 - 0.7 * 11.0)).abs() < 1e-12);
+        assert!(treated.humidity < humid.humidity);
+        assert_eq!(treated.regeneration_temp_k, humid.regeneration_temp_k);
+        let vapor = |c: &OperatingConditions| {
+            relative_to_absolute_humidity(c.humidity, c.ambient_temp_k, 1.0)
+        };
+        let return_vapor = relative_to_absolute_humidity(0.5, 297.0, 1.0);
+        let expected = vapor(&humid) - 0.6 * (vapor(&humid) - return_vapor);
+        assert!((vapor(&treated) - expected).abs() < 1e-9);
+
+        // The bed adsorbs from the preconditioned air
+        let properties = MOFThermalProperties::default();
+        let raw = ThermalFluidSimulator::new(properties.clone(), humid.clone());
+        let downstream = ThermalFluidSimulator::new(properties.clone(), treated.clone());
+        let expected_uptake = IsothermModel::langmuir(properties.langmuir_k).uptake_fraction_at(
+            treated.humidity,
+            treated.ambient_temp_k,
+            properties.heat_of_adsorption,
+        );
+        assert!((downstream.estimate_uptake_fraction() - expected_uptake).abs() < 1e-12);
+        // Drier, but cooler air binds more strongly, so uptake differs either way
+        let shift = downstream.estimate_uptake_fraction() - raw.estimate_uptake_fraction();
+        assert!(shift.abs() > 1e-3);
+
+        let bypass = ErvStage::new(0.0, 0.0).unwrap().precondition(&humid, 297.0, 0.5);
+        assert_eq!(bypass.ambient_temp_k, 308.0);
+        assert!((bypass.humidity - 0.8).abs() < 1e-12);
+        for (sensible, latent) in [(-0.1, 0.5), (0.5, 1.1), (f64::NAN, 0.5)] {
+            assert!(matches!(
+                ErvStage::new(sensible, latent),
+                Err(ThermalError::InvalidParameter(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_simulate_timeseries() {
+        let simulator = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        );
+        // A humid night warming into a drier afternoon
+        let weather: Vec<WeatherSample> = (0..24)
+            .map(|hour| {
+                let phase = (hour as f64 / 24.0 * std::f64::consts::TAU).sin();
+                WeatherSample {
+                    temp_k: 293.0 + 6.0 * phase,
+                    humidity: 0.55 - 0.2 * phase,
+                    pressure_atm: 1.0,
+                }
+            })
+            .collect();
+
+        let summary = simulator.simulate_timeseries(&weather);
+        let per_sample: Vec<SimulationResults> = weather
+            .iter()
+            .map(|sample| {
+                let conditions = OperatingConditions {
+                    ambient_temp_k: sample.temp_k,
+                    humidity: sample.humidity,
+                    pressure_atm: sample.pressure_atm,
+                    ..Default::default()
+                };
+                ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions)
+                    .simulate_temperature_swing()
+                    .unwrap()
+            })
+            .collect();
+
+        assert_eq!((summary.samples, summary.failed_samples), (24, 0));
+        let total: f64 = per_sample.iter().map(|r| r.water_yield_kg).sum();
+        assert!((summary.water_yield_kg - total).abs() < 1e-12);
+        let energy: f64 = per_sample.iter().map(|r| r.energy_consumption_kj).sum();
+        assert!((summary.energy_consumption_kj - energy).abs() < 1e-9);
+        assert!(per_sample.iter().all(|r| r.risk_score <= summary.max_risk_score));
+
+        let mut bad = weather[..2].to_vec();
+        bad[1].humidity = 1.5;
+        let summary = simulator.simulate_timeseries(&bad);
+        assert_eq!((summary.samples, summary.failed_samples), (1, 1));
+        assert_eq!(summary.water_yield_kg, per_sample[0].water_yield_kg);
+    }
+
+    #[test]
+    fn test_min_viable_regeneration_temp() {
+        let simulator = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        )
+        .with_hysteresis_factor(1.5);
+        let threshold = simulator.min_viable_regeneration_temp();
+        assert!(threshold > 299.0 && threshold < 573.0);
+
+        let yield_at = |regeneration_temp_k: f64| {
+            let conditions = OperatingConditions {
+                regeneration_temp_k,
+                ..Default::default()
+            };
+            simulator
+                .with_conditions(conditions)
+                .simulate_temperature_swing()
+                .unwrap()
+                .water_yield_kg
+        };
+        assert!(yield_at(threshold - 0.5).abs() < 1e-12);
+        assert!(yield_at(threshold + 0.5) > 0.0);
+
+        // Without hysteresis any swing above ambient desorbs water
+        let reversible = simulator.clone().with_hysteresis_factor(1.0);
+        assert!((reversible.min_viable_regeneration_temp() - 298.0).abs() < 1e-3);
+
+        let dry = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions { humidity: 0.0, ..Default::default() },
+        );
+        assert_eq!(dry.min_viable_regeneration_temp(), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_stability_policy() {
+        let properties = MOFThermalProperties::default();
+        let at = |regeneration_temp_k: f64| {
+            ThermalFluidSimulator::new(
+                properties.clone(),
+                OperatingConditions { regeneration_temp_k, ..Default::default() },
+            )
+        };
+        let stability = properties.thermal_stability_k;
+
+        let below = at(stability - 1.0).simulate_temperature_swing().unwrap();
+        assert!(!below.degraded && below.water_yield_kg > 0.0);
+
+        // The boundary counts as exceeded, as does a setpoint clearly above it
+        for regeneration_temp_k in [stability, stability + 50.0] {
+            let simulator = at(regeneration_temp_k);
+            assert_eq!(simulator.stability_policy(), StabilityPolicy::Degrade);
+            let results = simulator.simulate_temperature_swing().unwrap();
+            assert!(results.degraded);
+            assert_eq!(results.water_yield_kg, 0.0);
+            assert_eq!(results.risk_score, 1.0);
+            assert!(results.sensible_energy_kj > 0.0);
+
+            let err = simulator
+                .with_stability_policy(StabilityPolicy::Error)
+                .simulate_temperature_swing()
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                ThermalError::StabilityExceeded { regeneration_temp_k: t, .. }
+                    if t == regeneration_temp_k
+            ));
+        }
+        assert!(at(stability - 1.0)
+            .with_stability_policy(StabilityPolicy::Error)
+            .simulate_temperature_swing()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_try_new_validates_inputs() {
+        let simulator = ThermalFluidSimulator::try_new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        )
+        .unwrap();
+        assert_results_close(
+            &simulator.simulate_temperature_swing().unwrap(),
+            &ThermalFluidSimulator::default().simulate_temperature_swing().unwrap(),
+            0.0,
+        );
+
+        let err = ThermalFluidSimulator::try_new(
+            MOFThermalProperties::default().with_density(-600.0),
+            OperatingConditions::default(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ThermalError::InvalidProperties(_)));
+        assert!(err.to_string().contains("density"));
+
+        let humid = OperatingConditions { humidity: 1.5, ..Default::default() };
+        assert!(matches!(
+            ThermalFluidSimulator::try_new(MOFThermalProperties::default(), humid),
+            Err(ThermalError::InvalidConditions(_))
+        ));
+    }
+
+    #[test]
+    fn test_simulation_context_matches_fresh_runs() {
+        let simulator = ThermalFluidSimulator::default()
+            .with_hysteresis_factor(1.2)
+            .with_heat_exchanger(HeatExchanger::new(0.5).unwrap());
+        let context = simulator.context().unwrap();
+
+        for regeneration_temp_k in [320.0, 373.0, 420.0, 480.0, 600.0] {
+            let conditions = OperatingConditions {
+                regeneration_temp_k,
+                ..Default::default()
+            };
+            let fresh = simulator
+                .with_conditions(conditions)
+                .simulate_temperature_swing()
+                .unwrap();
+            assert_eq!(context.with_regeneration_temp(regeneration_temp_k).unwrap(), fresh);
+        }
+
+        assert!(matches!(
+            context.with_regeneration_temp(f64::NAN),
+            Err(ThermalError::InvalidConditions(_))
+        ));
+        let invalid = ThermalFluidSimulator::default().with_hysteresis_factor(0.5);
+        assert!(matches!(invalid.context(), Err(ThermalError::InvalidParameter(_))));
+    }
+
+    #[test]
+    fn test_condenser_temperature_limits_yield() {
+        let with_condenser = |condenser_k: Option<f64>| {
+            let conditions = OperatingConditions {
+                condensation_temperature_k: condenser_k,
+                ..Default::default()
+            };
+            ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions)
+                .simulate_temperature_swing()
+                .unwrap()
+        };
+        let ideal = with_condenser(None);
+        let cold = with_condenser(Some(283.0));
+        let warm = with_condenser(Some(330.0));
+
+        assert_eq!(ideal.condensed_fraction, 1.0);
+        assert!(warm.condensed_fraction < cold.condensed_fraction);
+        assert!(warm.water_yield_kg < cold.water_yield_kg);
+        assert!(cold.water_yield_kg < ideal.water_yield_kg);
+        let expected = ideal.water_yield_kg * cold.condensed_fraction;
+        assert!((cold.water_yield_kg - expected).abs() < 1e-12);
+        // The same vapor is desorbed either way, so the energy is unchanged
+        assert_eq!(warm.energy_consumption_kj, cold.energy_consumption_kj);
+
+        // A condenser at the peak bed temperature liquefies nothing
+        let hot = with_condenser(Some(ideal.max_temperature_k));
+        assert_eq!(hot.water_yield_kg, 0.0);
+
+        let invalid = OperatingConditions::builder().condensation_temperature_k(-5.0).build();
+        assert!(matches!(invalid, Err(ThermalError::InvalidConditions(_))));
+    }
+
+    #[test]
+    fn test_compare_to_baseline() {
+        let baseline = ThermalFluidSimulator::default().simulate_temperature_swing().unwrap();
+        let improved = SimulationResults {
+            water_yield_kg: baseline.water_yield_kg * 1.25,
+            energy_consumption_kj: baseline.energy_consumption_kj * 0.8,
+            thermal_efficiency: baseline.thermal_efficiency * 1.5,
+            risk_score: baseline.risk_score * 0.5,
+            ..baseline.clone()
+        };
+        let report = improved.compare_to_baseline(&baseline);
+        let close = |pct: Option<f64>, expected: f64| (pct.unwrap() - expected).abs() < 1e-9;
+        assert!(close(report.water_yield_pct, 25.0));
+        assert!(close(report.energy_consumption_pct, -20.0));
+        assert!(close(report.thermal_efficiency_pct, 50.0));
+        assert!(close(report.risk_score_pct, -50.0));
+        assert!(close(baseline.compare_to_baseline(&baseline).water_yield_pct, 0.0));
+
+        let dry = SimulationResults {
+            water_yield_kg: 0.0,
+            thermal_efficiency: 0.0,
+            ..baseline.clone()
+        };
+        let report = baseline.compare_to_baseline(&dry);
+        assert_eq!((report.water_yield_pct, report.thermal_efficiency_pct), (None, None));
+        assert!(close(report.risk_score_pct, 0.0));
+    }
+
+    #[test]
+    fn test_sweep_grid() {
+        let simulator = ThermalFluidSimulator::default();
+        // 290 K is below the default 298 K ambient, so its row fails
+        let temp_axis = [290.0, 350.0, 373.0];
+        let humidity_axis = [0.2, 0.4, 0.6, 1.5];
+        let grid = simulator.sweep_grid(&temp_axis, &humidity_axis);
+
+        assert_eq!(grid.cells.len(), temp_axis.len() * humidity_axis.len());
+        assert!(grid.get(0, 1).is_none());
+        assert!(grid.get(1, 3).is_none());
+        assert!(grid.get(3, 0).is_none());
+
+        let conditions = OperatingConditions {
+            regeneration_temp_k: 373.0,
+            humidity: 0.6,
+            ..OperatingConditions::default()
+        };
+        let direct = simulator.with_conditions(conditions).simulate_temperature_swing().unwrap();
+        assert_eq!(grid.get(2, 2), Some(&direct));
+    }
+
+    #[test]
+    fn test_default_for_class() {
+        let classes = [MofClass::Zirconium, MofClass::Aluminum, MofClass::Copper];
+        let properties: Vec<_> =
+            classes.iter().map(|&class| MOFThermalProperties::default_for_class(class)).collect();
+        for (i, a) in properties.iter().enumerate() {
+            a.validate().unwrap();
+            for b in &properties[i + 1..] {
+                assert_ne!(a.specific_heat, b.specific_heat);
+                assert_ne!(a.thermal_stability_k, b.thermal_stability_k);
+            }
+        }
+        // Zr frameworks outlast Cu paddlewheels
+        assert!(properties[0].thermal_stability_k > properties[2].thermal_stability_k);
+    }
+
+    #[test]
+    fn test_optimize_with_objective() {
+        let config = OptimizationConfig::default();
+        let by_efficiency = ThermalFluidSimulator::default()
+            .optimize_with_objective(&MaximizeEfficiency, &config)
+            .unwrap();
+        let by_yield = ThermalFluidSimulator::default()
+            .optimize_with_objective(&MaximizeYield, &config)
+            .unwrap();
+
+        // The default objective reproduces optimize_conditions
+        let mut default = ThermalFluidSimulator::default();
+        let report = default.optimize_conditions_report(&config).unwrap();
+        assert_eq!(
+            report.conditions.regeneration_temp_k,
+            by_efficiency.conditions.regeneration_temp_k
+        );
+        assert!(
+            by_yield.conditions.regeneration_temp_k > by_efficiency.conditions.regeneration_temp_k,
+            "yield optimum {} K not above efficiency optimum {} K",
+            by_yield.conditions.regeneration_temp_k,
+            by_efficiency.conditions.regeneration_temp_k
+        );
+        assert!(by_yield.best.water_yield_kg > by_efficiency.best.water_yield_kg);
+    }
+
+    #[test]
+    fn test_sweep_keeps_fractional_upper_bound() {
+        // 0.85 × 471.65 K = 400.9 K: truncating the bound to 400 K would end
+        // the 1 K sweep at 399 K, below the yield optimum at 400 K
+        let properties = MOFThermalProperties::default().with_thermal_stability_k(471.65);
+        let config = OptimizationConfig {
+            temp_step_k: 1.0,
+            ..Default::default()
+        };
+        let mut simulator = ThermalFluidSimulator::new(properties, OperatingConditions::default());
+        assert_eq!(simulator.sweep_candidates(&config).last(), Some(&400.0));
+
+        let report = simulator.optimize_with_objective(&MaximizeYield, &config).unwrap();
+        assert_eq!(report.conditions.regeneration_temp_k, 400.0);
+    }
+
+    #[test]
+    fn test_humidity_profile() {
+        let simulator = ThermalFluidSimulator::default();
+        let humidity = simulator.conditions.humidity;
+        let constant = simulator.simulate_with_humidity_profile(&[humidity; 12]).unwrap();
+        let direct = simulator.simulate_temperature_swing().unwrap();
+        assert_results_close(&constant, &direct, 1e-12);
+
+        // A dry-then-humid cycle lands between the two constant profiles
+        let at = |h: f64| simulator.simulate_with_humidity_profile(&[h]).unwrap().water_yield_kg;
+        let mixed = simulator.simulate_with_humidity_profile(&[0.2, 0.8]).unwrap();
+        assert!(at(0.2) < mixed.water_yield_kg && mixed.water_yield_kg < at(0.8));
+
+        assert!(simulator.simulate_with_humidity_profile(&[]).is_err());
+        assert!(simulator.simulate_with_humidity_profile(&[0.5, 1.5]).is_err());
+    }
+
+    #[test]
+    fn test_full_report() {
+        let simulator = ThermalFluidSimulator::default();
+        let report = simulator.full_report().unwrap();
+        assert_eq!(report.results, simulator.simulate_temperature_swing().unwrap());
+        assert_eq!(report.risk, simulator.risk_report().unwrap());
+        assert_eq!(report.energy.net_kj, report.results.energy_consumption_kj);
+
+        let text = report.to_string();
+        assert!(text.starts_with(&report.summary));
+        assert!(text.contains(&report.risk.assessment));
+    }
+
+    #[test]
+    fn test_validate_energy_balance() {
+        let simulator = ThermalFluidSimulator::default()
+            .with_heat_exchanger(HeatExchanger::new(0.5).unwrap());
+        let results = simulator.simulate_temperature_swing().unwrap();
+        assert!(results.recovered_energy_kj > 0.0);
+        simulator.validate_energy_balance(&results).unwrap();
+
+        // A sign error on recovery no longer adds up
+        let mismatched = SimulationResults {
+            energy_consumption_kj: results.sensible_energy_kj
+                + results.adsorption_energy_kj
+                + results.recovered_energy_kj,
+            ..results.clone()
+        };
+        assert!(matches!(
+            simulator.validate_energy_balance(&mismatched),
+            Err(ThermalError::EnergyImbalance(_))
+        ));
+
+        let negative = SimulationResults {
+            adsorption_energy_kj: -results.adsorption_energy_kj,
+            energy_consumption_kj: results.sensible_energy_kj
+                - results.adsorption_energy_kj
+                - results.recovered_energy_kj,
+            ..results
+        };
+        let err = simulator.validate_energy_balance(&negative).unwrap_err();
+        assert!(err.to_string().contains("adsorption_energy_kj must be non-negative"));
+    }
+
+    #[test]
+    fn test_specific_energy_consumption() {
+        // Containment-bay air: warm and humid, regenerated with reactor waste heat
+        let conditions = OperatingConditions {
+            ambient_temp_k: 308.0,
+            regeneration_temp_k: 393.0,
+            humidity: 0.7,
+            ..OperatingConditions::default()
+        };
+        let simulator = ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions);
+        let results = simulator.simulate_temperature_swing().unwrap();
+        let sec = results.specific_energy_consumption_kwh_per_m3();
+        // At least the latent heat of water (~627 kWh/m³) without heat recovery;
+        // sorbent harvesters report up to a few thousand
+        assert!((600.0..5000.0).contains(&sec), "SEC {} kWh/m³", sec);
+        assert!((sec - KG_WATER_PER_M3 * results.energy_per_liter_kwh).abs() < 1e-9 * sec);
+
+        let doubled = SimulationResults {
+            energy_consumption_kj: 2.0 * results.energy_consumption_kj,
+            ..results.clone()
+        };
+        assert!((doubled.specific_energy_consumption_kwh_per_m3() - 2.0 * sec).abs() < 1e-9 * sec);
+
+        let dry = SimulationResults {
+            water_yield_kg: 0.0,
+            ..results
+        };
+        assert_eq!(dry.specific_energy_consumption_kwh_per_m3(), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_aggregate_region_is_deterministic() {
+        let mut store = MofPropertyStore::new();
+        let fips_list: Vec<u32> = (1..=2000).collect();
+        for &fips in &fips_list {
+            let spread = f64::from(fips % 97) / 97.0;
+            store.insert(MOFThermalProperties {
+                fips,
+                thermal_conductivity: 0.3 + 0.4 * spread,
+                max_uptake_g_per_g: 0.2 + 0.2 * spread,
+                ..Default::default()
+            });
+        }
+        let conditions = OperatingConditions::default();
+
+        let first = aggregate_region(&store, &fips_list, &conditions).unwrap();
+        let second = aggregate_region(&store, &fips_list, &conditions).unwrap();
+        assert_eq!(first.water_yield_kg.to_bits(), second.water_yield_kg.to_bits());
+        assert_eq!(first.energy_consumption_kj.to_bits(), second.energy_consumption_kj.to_bits());
+
+        // Same bits as a serial sum in list order
+        let serial = simulate_batch(&store, &fips_list, &conditions, None)
+            .into_iter()
+            .fold(0.0, |total, (_, results)| total + results.unwrap().water_yield_kg);
+        assert_eq!(first.water_yield_kg.to_bits(), serial.to_bits());
+    }
+
+    #[test]
+    fn test_airflow_limits_uptake() {
+        let starved = OperatingConditions {
+            airflow_m3_per_s: Some(1e-4),
+            ..OperatingConditions::default()
+        };
+        let air_kg = 1e-4 * 1800.0 * relative_to_absolute_humidity(0.4, 298.0, 1.0) / G_PER_KG;
+        for max_uptake in [0.3, 1.0] {
+            let properties = MOFThermalProperties::default().with_max_uptake_g_per_g(max_uptake);
+            let unlimited =
+                ThermalFluidSimulator::new(properties.clone(), OperatingConditions::default())
+                    .simulate_temperature_swing()
+                    .unwrap();
+            let results = ThermalFluidSimulator::new(properties, starved.clone())
+                .simulate_temperature_swing()
+                .unwrap();
+            // Capacity no longer matters once the air runs dry
+            assert!(unlimited.water_yield_kg > air_kg);
+            assert!((results.water_yield_kg - air_kg).abs() < 1e-12);
+        }
+
+        // Ample air leaves the capacity-limited result unchanged
+        let ample = OperatingConditions {
+            airflow_m3_per_s: Some(10.0),
+            ..OperatingConditions::default()
+        };
+        let simulator = ThermalFluidSimulator::default();
+        assert_eq!(
+            simulator.with_conditions(ample).simulate_temperature_swing().unwrap(),
+            simulator.simulate_temperature_swing().unwrap()
+        );
+        assert!(OperatingConditions::builder().airflow_m3_per_s(0.0).build().is_err());
+    }
+
+    #[test]
+    fn test_limiting_factor() {
+        let simulator = ThermalFluidSimulator::default();
+        assert_eq!(simulator.limiting_factor().unwrap(), LimitingFactor::EquilibriumCapacity);
+
+        let starved = simulator.with_conditions(OperatingConditions {
+            airflow_m3_per_s: Some(1e-4),
+            ..OperatingConditions::default()
+        });
+        assert_eq!(starved.limiting_factor().unwrap(), LimitingFactor::Airflow);
+
+        // A 2-minute cycle heats for one minute against a 120 s time constant;
+        // fast kinetics keep mass transfer out of the way
+        let fast_kinetics = MOFThermalProperties::default().with_mass_transfer_coeff(1.0);
+        let short_cycle = OperatingConditions {
+            cycle_time_seconds: 120.0,
+            ..OperatingConditions::default()
+        };
+        let rushed = ThermalFluidSimulator::new(fast_kinetics, short_cycle);
+        assert_eq!(rushed.limiting_factor().unwrap(), LimitingFactor::CycleTime);
+
+        let slow_kinetics = MOFThermalProperties::default().with_mass_transfer_coeff(1e-4);
+        let slow = ThermalFluidSimulator::new(slow_kinetics, OperatingConditions::default());
+        assert_eq!(slow.limiting_factor().unwrap(), LimitingFactor::MassTransfer);
+
+        assert_eq!(starved.full_report().unwrap().limiting_factor, Some(LimitingFactor::Airflow));
+
+        // Invalid inputs are rejected rather than classified
+        let no_cycle = simulator.with_conditions(OperatingConditions {
+            cycle_time_seconds: 0.0,
+            ..OperatingConditions::default()
+        });
+        assert!(matches!(no_cycle.limiting_factor(), Err(ThermalError::InvalidConditions(_))));
+
+        // A degraded run yields nothing, so nothing limits it
+        let overheated = simulator.with_conditions(OperatingConditions {
+            regeneration_temp_k: MOFThermalProperties::default().thermal_stability_k,
+            ..OperatingConditions::default()
+        });
+        assert!(matches!(
+            overheated.limiting_factor(),
+            Err(ThermalError::StabilityExceeded { .. })
+        ));
+        let report = overheated.full_report().unwrap();
+        assert!(report.results.degraded);
+        assert_eq!(report.limiting_factor, None);
+        assert!(report.to_string().contains("limited by: framework degraded"));
+    }
+
+    #[test]
+    fn test_json_key_case() {
+        let results = ThermalFluidSimulator::default().simulate_temperature_swing().unwrap();
+        let keys = |case: KeyCase| -> Vec<String> {
+            let json = results.to_json_string_with_case(case).unwrap();
+            let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+            let mut keys: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+            keys.sort();
+            keys
+        };
+        let sorted = |names: &[&str]| {
+            let mut names: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(keys(KeyCase::Snake), sorted(&RESULTS_CSV_COLUMNS));
+        assert_eq!(
+            results.to_json_string_with_case(KeyCase::Snake).unwrap(),
+            results.to_json_string().unwrap()
+        );
+        assert_eq!(
+            keys(KeyCase::Camel),
+            sorted(&[
+                "waterYieldKg",
+                "uptakeEfficiency",
+                "massTransferLimited",
+                "energyConsumptionKj",
+                "sensibleEnergyKj",
+                "adsorptionEnergyKj",
+                "recoveredEnergyKj",
+                "maxTemperatureK",
+                "stabilityMarginK",
+                "coolingTimeSeconds",
+                "insufficientCycleTime",
+                "lumpedModelInvalid",
+                "degraded",
+                "condensedFraction",
+                "thermalEfficiency",
+                "efficiencyMode",
+                "coefficientOfPerformance",
+                "energyPerLiterKwh",
+                "riskScore",
+                "schemaVersion",
+            ])
+        );
+
+        let camel = results.to_json_string_with_case(KeyCase::Camel).unwrap();
+        let restored = SimulationResults::from_json_str_with_case(&camel, KeyCase::Camel).unwrap();
+        assert_eq!(restored, results);
+    }
+
+    #[test]
+    fn test_steady_state_detection() {
+        // Changes of 0.5^i kg first drop below 0.01 kg at cycle 7
+        let yields: Vec<f64> = (0..12).map(|i| 1.0 + 0.5f64.powi(i)).collect();
+        assert_eq!(steady_state_cycle(&yields, 0.01), Some(7));
+        assert_eq!(steady_state_cycle(&yields, 1e-9), None);
+
+        let conditions = OperatingConditions {
+            capacity_decay_per_cycle: 0.2,
+            ..OperatingConditions::default()
+        };
+        let simulator = ThermalFluidSimulator::new(MOFThermalProperties::default(), conditions);
+        let summary = simulator.simulate_cycles_summary(40, 1e-4).unwrap();
+        let yields: Vec<f64> = summary.cycles.iter().map(|r| r.water_yield_kg).collect();
+        let start = summary.steady_state_cycle.unwrap();
+        assert!(start > 1);
+        assert!((yields[start] - yields[start - 1]).abs() < 1e-4);
+        assert!((yields[start - 1] - yields[start - 2]).abs() >= 1e-4);
+        let mean = yields[start..].iter().sum::<f64>() / (40 - start) as f64;
+        assert!((summary.steady_state_yield_kg.unwrap() - mean).abs() < 1e-15);
+
+        assert!(simulator.simulate_cycles_summary(10, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_partial_desorption() {
+        let full = ThermalFluidSimulator::default().simulate_temperature_swing().unwrap();
+        let half_conditions =
+            OperatingConditions::builder().desorption_fraction(0.5).build().unwrap();
+        let half = ThermalFluidSimulator::new(MOFThermalProperties::default(), half_conditions)
+            .simulate_temperature_swing()
+            .unwrap();
+
+        assert!((half.water_yield_kg / full.water_yield_kg - 0.5).abs() < 1e-9);
+        assert!((half.energy_consumption_kj / full.energy_consumption_kj - 0.5).abs() < 1e-9);
+        // Efficiency is a ratio of the two, so it is unchanged
+        assert!((half.thermal_efficiency - full.thermal_efficiency).abs() < 1e-15);
+
+        for invalid in [0.0, -0.5, 1.5, f64::NAN] {
+            let result = OperatingConditions::builder().desorption_fraction(invalid).build();
+            assert!(matches!(result, Err(ThermalError::InvalidConditions(_))));
+        }
+    }
+
+    #[test]
+    fn test_required_bed_mass_with_airflow() {
+        // Ample air: the sized bed meets the target without hitting the cap
+        let ample = ThermalFluidSimulator::default().with_conditions(OperatingConditions {
+            airflow_m3_per_s: Some(1.0),
+            ..OperatingConditions::default()
+        });
+        let bed_mass_kg = ample.required_bed_mass_kg(5.0).unwrap();
+        let sized = ample.with_conditions(OperatingConditions {
+            bed_mass_kg,
+            ..ample.conditions.clone()
+        });
+        assert!((sized.daily_water_production_kg().unwrap() - 5.0).abs() < 1e-9);
+
+        // 1 L/s of air carries well under 5 kg/day, whatever the bed mass
+        let starved = ThermalFluidSimulator::default().with_conditions(OperatingConditions {
+            airflow_m3_per_s: Some(1e-3),
+            ..OperatingConditions::default()
+        });
+        assert!(matches!(
+            starved.required_bed_mass_kg(5.0),
+            Err(ThermalError::InvalidConditions(_))
+        ));
+    }
+
+    #[test]
+    fn test_pressure_swing_airflow_cap() {
+        let starved = ThermalFluidSimulator::new(
+            load_mof_properties(1005).unwrap(),
+            OperatingConditions {
+                airflow_m3_per_s: Some(1e-3),
+                ..OperatingConditions::default()
+            },
+        );
+        let unlimited = starved.with_conditions(OperatingConditions::default());
+        let air_kg = starved.supplied_water_kg();
+
+        assert!(unlimited.simulate_pressure_swing(0.1).unwrap().water_yield_kg > air_kg);
+        let psa = starved.simulate_pressure_swing(0.1).unwrap();
+        assert!((psa.water_yield_kg - air_kg).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_pressure_swing_partial_desorption() {
+        let full = ThermalFluidSimulator::new(
+            load_mof_properties(1005).unwrap(),
+            OperatingConditions::default(),
+        );
+        let half = full.with_conditions(OperatingConditions {
+            desorption_fraction: 0.5,
+            ..OperatingConditions::default()
+        });
+        let full = full.simulate_pressure_swing(0.1).unwrap();
+        let half = half.simulate_pressure_swing(0.1).unwrap();
+
+        assert!((half.water_yield_kg / full.water_yield_kg - 0.5).abs() < 1e-9);
+        assert!((half.energy_consumption_kj / full.energy_consumption_kj - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pressure_swing_condenser() {
+        let ideal = ThermalFluidSimulator::new(
+            load_mof_properties(1005).unwrap(),
+            OperatingConditions::default(),
+        );
+        let chilled = ideal.with_conditions(OperatingConditions {
+            condensation_temperature_k: Some(283.15),
+            ..OperatingConditions::default()
+        });
+        let ideal = ideal.simulate_pressure_swing(0.1).unwrap();
+        let psa = chilled.simulate_pressure_swing(0.1).unwrap();
+
+        let fraction = chilled.condensed_fraction(chilled.conditions.ambient_temp_k);
+        assert!(fraction > 0.0 && fraction < 1.0);
+        assert_eq!(psa.condensed_fraction, fraction);
+        assert!((psa.water_yield_kg - ideal.water_yield_kg * fraction).abs() < 1e-15);
+        // The pump still lifts every kilogram desorbed
+        assert_eq!(psa.energy_consumption_kj, ideal.energy_consumption_kj);
     }
 }
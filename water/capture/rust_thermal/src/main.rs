@@ -0,0 +1,81 @@
+//! Command-line entry point for MOF thermal-fluid simulations
+//!
+//! Loads properties for a FIPS code, runs a temperature-swing simulation, and
+//! prints the results as JSON. With `--optimize`, prints the optimized
+//! operating conditions instead.
+
+use clap::Parser;
+use mof_thermal_dynamics::*;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Debug, Parser)]
+#[command(version, about = "MOF thermal-fluid dynamics simulator")]
+struct Args {
+    /// County FIPS code of the MOF site
+    #[arg(long)]
+    fips: u32,
+
+    /// CSV of MOF thermal properties keyed by FIPS (defaults to placeholder properties)
+    #[arg(long)]
+    properties_csv: Option<PathBuf>,
+
+    /// Ambient temperature (K)
+    #[arg(long, default_value_t = 298.0)]
+    ambient: f64,
+
+    /// Regeneration temperature (K)
+    #[arg(long, default_value_t = 373.0)]
+    regen: f64,
+
+    /// Relative humidity (0-1)
+    #[arg(long, default_value_t = 0.4)]
+    humidity: f64,
+
+    /// Operating pressure (atm)
+    #[arg(long, default_value_t = 1.0)]
+    pressure: f64,
+
+    /// Cycle time (s)
+    #[arg(long, default_value_t = 3600.0)]
+    cycle_time: f64,
+
+    /// Optimize regeneration temperature and print the chosen conditions
+    #[arg(long)]
+    optimize: bool,
+}
+
+fn run(args: Args) -> Result<String, ThermalError> {
+    let properties = match &args.properties_csv {
+        Some(path) => load_mof_properties_from_csv(path, args.fips)?,
+        None => load_mof_properties(args.fips)?,
+    };
+    let conditions = OperatingConditions::builder()
+        .ambient_temp_k(args.ambient)
+        .regeneration_temp_k(args.regen)
+        .humidity(args.humidity)
+        .pressure_atm(args.pressure)
+        .cycle_time_seconds(args.cycle_time)
+        .build()?;
+
+    let mut simulator = ThermalFluidSimulator::new(properties, conditions);
+    if args.optimize {
+        let optimized = simulator.optimize_conditions()?;
+        Ok(serde_json::to_string_pretty(&optimized)?)
+    } else {
+        simulator.simulate_temperature_swing()?.to_json_string()
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(json) => {
+            println!("{}", json);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
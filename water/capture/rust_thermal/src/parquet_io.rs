@@ -0,0 +1,143 @@
+//! Parquet property loading for the columnar Python pipeline output
+//!
+//! Enabled with the `parquet` feature. Column names match the
+//! `MOFThermalProperties` fields, as in the CSV loader; `fips` may be any
+//! integer column and the remaining columns any integer or float column.
+//! Optional columns take their serde defaults when absent or null.
+
+use crate::{
+    default_mass_transfer_coeff, MOFThermalProperties, ThermalError, OPTIONAL_PROPERTY_COLUMNS,
+    PROPERTY_COLUMNS,
+};
+use parquet::errors::ParquetError;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::record::Field;
+use std::fs::File;
+use std::path::Path;
+
+/// Load MOF properties for a FIPS code from a Parquet file
+///
+/// Every row is validated, so a missing value or non-numeric column anywhere
+/// in the file is an error. If a FIPS code repeats, the first row wins.
+pub fn load_mof_properties_from_parquet<P: AsRef<Path>>(
+    path: P,
+    fips: u32,
+) -> Result<MOFThermalProperties, ThermalError> {
+    let reader = SerializedFileReader::new(File::open(path)?)?;
+
+    let schema = reader.metadata().file_metadata().schema_descr();
+    for name in PROPERTY_COLUMNS {
+        if !schema.columns().iter().any(|column| column.name() == name) {
+            return Err(parquet_error(format!("missing required column '{}'", name)));
+        }
+    }
+
+    let mut found = None;
+    for (index, row) in reader.get_row_iter(None)?.enumerate() {
+        let row = row?;
+        let mut values = [None; PROPERTY_COLUMNS.len()];
+        let mut optional = [None; OPTIONAL_PROPERTY_COLUMNS.len()];
+        for (name, field) in row.get_column_iter() {
+            if let Some(i) = PROPERTY_COLUMNS.iter().position(|column| column == name) {
+                values[i] = Some(field);
+            } else if let Some(i) = OPTIONAL_PROPERTY_COLUMNS.iter().position(|c| c == name) {
+                optional[i] = Some(field);
+            }
+        }
+        let number = |i: usize| -> Result<f64, ThermalError> {
+            values[i].and_then(field_to_f64).ok_or_else(|| {
+                parquet_error(format!(
+                    "row {}: missing or non-numeric value for '{}'",
+                    index, PROPERTY_COLUMNS[i]
+                ))
+            })
+        };
+
+        let optional_number = |i: usize, default: f64| -> Result<f64, ThermalError> {
+            match optional[i] {
+                None | Some(Field::Null) => Ok(default),
+                Some(field) => field_to_f64(field).ok_or_else(|| {
+                    parquet_error(format!(
+                        "row {}: non-numeric value for '{}'",
+                        index, OPTIONAL_PROPERTY_COLUMNS[i]
+                    ))
+                }),
+            }
+        };
+
+        let fips_value = number(0)?;
+        if fips_value.fract() != 0.0 || !(0.0..=u32::MAX as f64).contains(&fips_value) {
+            return Err(parquet_error(format!(
+                "row {}: invalid FIPS code '{}'",
+                index, fips_value
+            )));
+        }
+        let properties = MOFThermalProperties {
+            fips: fips_value as u32,
+            thermal_conductivity: number(1)?,
+            specific_heat: number(2)?,
+            density: number(3)?,
+            thermal_stability_k: number(4)?,
+            heat_of_adsorption: number(5)?,
+            langmuir_k: number(6)?,
+            max_uptake_g_per_g: number(7)?,
+            mass_transfer_coeff: optional_number(0, default_mass_transfer_coeff())?,
+        };
+        if properties.fips == fips && found.is_none() {
+            found = Some(properties);
+        }
+    }
+
+    found.ok_or(ThermalError::FipsNotFound(fips))
+}
+
+/// Numeric value of a Parquet cell, if it holds one
+fn field_to_f64(field: &Field) -> Option<f64> {
+    match *field {
+        Field::Byte(v) => Some(v.into()),
+        Field::Short(v) => Some(v.into()),
+        Field::Int(v) => Some(v.into()),
+        Field::Long(v) => Some(v as f64),
+        Field::UByte(v) => Some(v.into()),
+        Field::UShort(v) => Some(v.into()),
+        Field::UInt(v) => Some(v.into()),
+        Field::ULong(v) => Some(v as f64),
+        Field::Float(v) => Some(v.into()),
+        Field::Double(v) => Some(v),
+        _ => None,
+    }
+}
+
+fn parquet_error(message: String) -> ThermalError {
+    ThermalError::Parquet(ParquetError::General(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_mof_properties_from_csv;
+    use crate::tests::fixture;
+
+    #[test]
+    fn test_parquet_matches_csv() {
+        for fips in [1001, 1003, 1005] {
+            let from_parquet =
+                load_mof_properties_from_parquet(fixture("mof_properties.parquet"), fips).unwrap();
+            let from_csv =
+                load_mof_properties_from_csv(fixture("mof_properties.csv"), fips).unwrap();
+            assert_eq!(from_parquet, from_csv);
+        }
+    }
+
+    #[test]
+    fn test_parquet_errors() {
+        let err = load_mof_properties_from_parquet(fixture("mof_properties.parquet"), 9999)
+            .unwrap_err();
+        assert!(matches!(err, ThermalError::FipsNotFound(9999)));
+
+        // A CSV is not a Parquet file
+        let err = load_mof_properties_from_parquet(fixture("mof_properties.csv"), 1001)
+            .unwrap_err();
+        assert!(matches!(err, ThermalError::Parquet(_)));
+    }
+}
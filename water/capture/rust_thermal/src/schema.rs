@@ -0,0 +1,80 @@
+//! JSON Schema for the types exchanged with the Python pipeline
+//!
+//! Enabled with the `schemars` feature. The schemas follow the serde
+//! representation, so fields with serde defaults are optional and the Python
+//! side can validate its JSON against the same contract the crate parses.
+
+use crate::{MOFThermalProperties, OperatingConditions, SimulationResults, ThermalError};
+use schemars::schema_for;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// JSON Schema for each public data type, keyed by type name
+pub fn json_schemas() -> Vec<(&'static str, serde_json::Value)> {
+    vec![
+        ("MOFThermalProperties", schema_for!(MOFThermalProperties).to_value()),
+        ("OperatingConditions", schema_for!(OperatingConditions).to_value()),
+        ("SimulationResults", schema_for!(SimulationResults).to_value()),
+    ]
+}
+
+/// Write `<TypeName>.schema.json` for each type in `json_schemas` into `dir`
+///
+/// Creates `dir` if needed and returns the paths written.
+pub fn write_json_schemas<P: AsRef<Path>>(dir: P) -> Result<Vec<PathBuf>, ThermalError> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    json_schemas()
+        .into_iter()
+        .map(|(name, schema)| {
+            let path = dir.join(format!("{}.schema.json", name));
+            fs::write(&path, serde_json::to_string_pretty(&schema)?)?;
+            Ok(path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field_names(value: serde_json::Value) -> Vec<String> {
+        value.as_object().unwrap().keys().cloned().collect()
+    }
+
+    #[test]
+    fn test_schemas_cover_every_field() {
+        let results = crate::ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        )
+        .simulate_temperature_swing()
+        .unwrap();
+        let expected = [
+            field_names(serde_json::to_value(MOFThermalProperties::default()).unwrap()),
+            field_names(serde_json::to_value(OperatingConditions::default()).unwrap()),
+            field_names(serde_json::to_value(results).unwrap()),
+        ];
+
+        for ((name, schema), fields) in json_schemas().into_iter().zip(expected) {
+            let properties = &schema["properties"];
+            for field in fields {
+                assert!(properties.get(&field).is_some(), "{} schema missing {}", name, field);
+            }
+        }
+    }
+
+    #[test]
+    fn test_write_json_schemas() {
+        let dir = std::env::temp_dir().join(format!("mof_thermal_schemas_{}", std::process::id()));
+        let paths = write_json_schemas(&dir).unwrap();
+        assert_eq!(paths.len(), 3);
+        for path in &paths {
+            let schema: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+            assert!(schema["properties"].is_object());
+        }
+        assert!(dir.join("SimulationResults.schema.json").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
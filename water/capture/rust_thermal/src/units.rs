@@ -0,0 +1,175 @@
+//! Unit-tagged quantities for the typed API
+//!
+//! Each newtype wraps a raw `f64` in the unit the crate's fields use, so a
+//! Celsius value or a mass cannot be passed where a temperature in kelvin is
+//! expected. Same-unit values add and subtract, scale by plain numbers, and
+//! divide to give a dimensionless ratio.
+
+use serde::{Deserialize, Serialize};
+use std::iter::Sum;
+use std::ops::{Add, Div, Mul, Sub};
+
+macro_rules! unit {
+    ($(#[$doc:meta])* $name:ident, $symbol:literal) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(pub f64);
+
+        impl $name {
+            /// Raw value in the unit's base scale
+            pub fn value(self) -> f64 {
+                self.0
+            }
+        }
+
+        impl From<$name> for f64 {
+            fn from(quantity: $name) -> f64 {
+                quantity.0
+            }
+        }
+
+        impl Add for $name {
+            type Output = $name;
+            fn add(self, rhs: $name) -> $name {
+                $name(self.0 + rhs.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = $name;
+            fn sub(self, rhs: $name) -> $name {
+                $name(self.0 - rhs.0)
+            }
+        }
+
+        impl Mul<f64> for $name {
+            type Output = $name;
+            fn mul(self, rhs: f64) -> $name {
+                $name(self.0 * rhs)
+            }
+        }
+
+        impl Div<f64> for $name {
+            type Output = $name;
+            fn div(self, rhs: f64) -> $name {
+                $name(self.0 / rhs)
+            }
+        }
+
+        impl Div for $name {
+            type Output = f64;
+            fn div(self, rhs: $name) -> f64 {
+                self.0 / rhs.0
+            }
+        }
+
+        impl Sum for $name {
+            fn sum<I: Iterator<Item = $name>>(iter: I) -> $name {
+                $name(iter.map(|quantity| quantity.0).sum())
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{} {}", self.0, $symbol)
+            }
+        }
+    };
+}
+
+unit!(
+    /// Absolute temperature (K)
+    Kelvin,
+    "K"
+);
+unit!(
+    /// Pressure (atm)
+    Atm,
+    "atm"
+);
+unit!(
+    /// Mass (kg)
+    Kilograms,
+    "kg"
+);
+unit!(
+    /// Energy (kJ)
+    KiloJoules,
+    "kJ"
+);
+
+impl Kelvin {
+    /// Convert from degrees Celsius
+    pub fn from_celsius(celsius: f64) -> Self {
+        Kelvin(crate::celsius_to_kelvin(celsius))
+    }
+
+    /// Value in degrees Celsius
+    pub fn to_celsius(self) -> f64 {
+        crate::kelvin_to_celsius(self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OperatingConditions;
+
+    #[test]
+    fn test_unit_arithmetic() {
+        assert_eq!(Kelvin(373.0) - Kelvin(298.0), Kelvin(75.0));
+        assert_eq!(Kilograms(1.5) + Kilograms(0.5), Kilograms(2.0));
+        assert_eq!(KiloJoules(10.0) * 3.0, KiloJoules(30.0));
+        assert_eq!(Atm(2.0) / 4.0, Atm(0.5));
+        assert_eq!(KiloJoules(30.0) / KiloJoules(10.0), 3.0);
+        let total: Kilograms = [Kilograms(1.0), Kilograms(2.0)].into_iter().sum();
+        assert_eq!(total, Kilograms(3.0));
+        assert_eq!(Kelvin::from_celsius(25.0), Kelvin(298.15));
+        assert!((Kelvin(373.15).to_celsius() - 100.0).abs() < 1e-9);
+        assert_eq!(Kelvin(298.0).to_string(), "298 K");
+        assert_eq!(serde_json::to_string(&Atm(1.5)).unwrap(), "1.5");
+    }
+
+    #[test]
+    fn test_typed_conditions_round_trip() {
+        let conditions = OperatingConditions::builder()
+            .ambient_temp(Kelvin::from_celsius(20.0))
+            .regeneration_temp(Kelvin(380.0))
+            .pressure(Atm(1.2))
+            .bed_mass(Kilograms(2.5))
+            .build()
+            .unwrap();
+
+        assert_eq!(conditions.ambient_temp_k, 293.15);
+        assert_eq!(conditions.regeneration_temp_k, 380.0);
+        assert_eq!(conditions.pressure_atm, 1.2);
+        assert_eq!(conditions.bed_mass_kg, 2.5);
+
+        assert_eq!(conditions.ambient_temp(), Kelvin(293.15));
+        assert_eq!(conditions.regeneration_temp(), Kelvin(380.0));
+        assert_eq!(conditions.pressure(), Atm(1.2));
+        assert_eq!(conditions.bed_mass(), Kilograms(2.5));
+        assert_eq!(f64::from(conditions.bed_mass()), conditions.bed_mass_kg);
+
+        let typed = OperatingConditions::from_units(
+            Kelvin(293.15),
+            Kelvin(380.0),
+            conditions.humidity,
+            Atm(1.2),
+            conditions.cycle_time_seconds,
+            Kilograms(2.5),
+        );
+        assert_eq!(
+            serde_json::to_value(&typed).unwrap(),
+            serde_json::to_value(&conditions).unwrap()
+        );
+
+        let results = crate::ThermalFluidSimulator::new(Default::default(), conditions)
+            .simulate_temperature_swing()
+            .unwrap();
+        assert_eq!(results.water_yield().value(), results.water_yield_kg);
+        assert_eq!(f64::from(results.energy_consumption()), results.energy_consumption_kj);
+        assert!(results.max_temperature() <= Kelvin(380.0));
+    }
+}
@@ -0,0 +1,81 @@
+//! Browser entry points for the ModelEarth grid frontend
+//!
+//! Enabled with the `wasm` feature. Inputs and outputs are JSON strings so the
+//! JavaScript side can pass plain objects through `JSON.stringify`. Errors are
+//! returned to JavaScript as string `JsValue`s carrying the `ThermalError`
+//! message.
+//!
+//! Exported functions:
+//!
+//! ```text
+//! simulateTemperatureSwing(propertiesJson: string, conditionsJson: string): string
+//! ```
+
+use crate::{MOFThermalProperties, OperatingConditions, ThermalError, ThermalFluidSimulator};
+use wasm_bindgen::prelude::*;
+
+/// Run one temperature-swing cycle from JSON inputs, returning JSON results
+///
+/// `properties_json` is a serialized `MOFThermalProperties` and
+/// `conditions_json` a serialized `OperatingConditions`; fields with serde
+/// defaults may be omitted.
+#[wasm_bindgen(js_name = simulateTemperatureSwing)]
+pub fn simulate_temperature_swing(
+    properties_json: &str,
+    conditions_json: &str,
+) -> Result<String, JsValue> {
+    simulate_json(properties_json, conditions_json)
+        .map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Native core of `simulate_temperature_swing`, kept free of `JsValue`
+fn simulate_json(properties_json: &str, conditions_json: &str) -> Result<String, ThermalError> {
+    let properties: MOFThermalProperties = serde_json::from_str(properties_json)?;
+    let conditions: OperatingConditions = serde_json::from_str(conditions_json)?;
+
+    ThermalFluidSimulator::new(properties, conditions)
+        .simulate_temperature_swing()?
+        .to_json_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::assert_results_close;
+    use crate::SimulationResults;
+
+    #[test]
+    fn test_simulate_json_round_trip() {
+        let properties = serde_json::to_string(&MOFThermalProperties::default()).unwrap();
+        let conditions = r#"{"ambient_temp_k": 298.0, "regeneration_temp_k": 373.0,
+            "humidity": 0.4, "pressure_atm": 1.0, "cycle_time_seconds": 3600.0}"#;
+
+        let json = simulate_json(&properties, conditions).unwrap();
+        let results: SimulationResults = serde_json::from_str(&json).unwrap();
+        assert!(results.water_yield_kg > 0.0);
+
+        let native = ThermalFluidSimulator::new(
+            MOFThermalProperties::default(),
+            OperatingConditions::default(),
+        )
+        .simulate_temperature_swing()
+        .unwrap();
+        assert_results_close(&results, &native, 0.0);
+    }
+
+    #[test]
+    fn test_simulate_json_reports_errors() {
+        let properties = serde_json::to_string(&MOFThermalProperties::default()).unwrap();
+        let conditions = serde_json::to_string(&OperatingConditions {
+            humidity: 1.5,
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert!(matches!(
+            simulate_json(&properties, &conditions),
+            Err(ThermalError::InvalidConditions(_))
+        ));
+        assert!(matches!(simulate_json("not json", &conditions), Err(ThermalError::Json(_))));
+    }
+}
@@ -0,0 +1,45 @@
+use assert_cmd::Command;
+use serde_json::Value;
+
+fn run_json(args: &[&str]) -> Value {
+    let output = Command::cargo_bin("mof_thermal_dynamics")
+        .unwrap()
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_cli_prints_simulation_results() {
+    let json = run_json(&["--fips", "1005", "--regen", "380", "--humidity", "0.5"]);
+
+    for key in [
+        "water_yield_kg",
+        "energy_consumption_kj",
+        "max_temperature_k",
+        "thermal_efficiency",
+        "risk_score",
+    ] {
+        assert!(json.get(key).is_some(), "missing key {}", key);
+    }
+    assert!(json["water_yield_kg"].as_f64().unwrap() > 0.0);
+}
+
+#[test]
+fn test_cli_optimize_prints_conditions() {
+    let json = run_json(&["--fips", "1005", "--optimize"]);
+
+    assert!(json.get("regeneration_temp_k").is_some());
+    assert_eq!(json["ambient_temp_k"].as_f64(), Some(298.0));
+}
+
+#[test]
+fn test_cli_reports_invalid_conditions() {
+    Command::cargo_bin("mof_thermal_dynamics")
+        .unwrap()
+        .args(["--fips", "1005", "--humidity", "1.5"])
+        .assert()
+        .failure();
+}
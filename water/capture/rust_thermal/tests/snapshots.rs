@@ -0,0 +1,45 @@
+//! Snapshot tests for full simulation outputs
+//!
+//! Any change to a numeric output shows up as a snapshot diff. Review with
+//! `cargo insta review` and accept only intended physics changes.
+
+use mof_thermal_dynamics::*;
+
+fn simulate(properties: MOFThermalProperties, regeneration_temp_k: f64) -> SimulationResults {
+    let conditions = OperatingConditions {
+        regeneration_temp_k,
+        ..Default::default()
+    };
+    ThermalFluidSimulator::new(properties, conditions)
+        .simulate_temperature_swing()
+        .unwrap()
+}
+
+#[test]
+fn snapshot_safe_configuration() {
+    // 373 K against a 573 K stability limit
+    let results = simulate(MOFThermalProperties::default(), 373.0);
+    assert!(results.risk_score < 0.1);
+    insta::assert_json_snapshot!("safe", results);
+}
+
+#[test]
+fn snapshot_moderate_risk_configuration() {
+    // Past 80% of the stability limit, below the default risk ceiling
+    let results = simulate(MOFThermalProperties::default(), 490.0);
+    assert!(results.risk_score > 0.1 && results.risk_score < 0.5);
+    insta::assert_json_snapshot!("moderate_risk", results);
+}
+
+#[test]
+fn snapshot_high_risk_configuration() {
+    // A fragile, high-capacity framework driven close to its limit
+    let properties = MOFThermalProperties {
+        thermal_stability_k: 450.0,
+        max_uptake_g_per_g: 0.4,
+        ..Default::default()
+    };
+    let results = simulate(properties, 440.0);
+    assert!(results.risk_score > 0.5);
+    insta::assert_json_snapshot!("high_risk", results);
+}